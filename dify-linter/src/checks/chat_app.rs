@@ -0,0 +1,310 @@
+use crate::checks::models::check_model;
+use crate::config::Config;
+use crate::rules;
+use crate::types::{DifyDsl, FormField, LintError, ModelConfig};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// JSON pointer to the model_config section
+const MODEL_CONFIG_PATH: &str = "/model_config";
+
+/// Check a chat, agent-chat or completion app
+pub fn check_chat_app(dsl: &DifyDsl, config: &Config) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let Some(mc) = &dsl.model_config else {
+        return errors;
+    };
+    let mode = dsl.mode().unwrap_or("chat");
+
+    errors.extend(check_app_model(mc, config));
+    errors.extend(check_prompt(mc));
+    errors.extend(check_input_form(mc));
+    errors.extend(check_datasets(mc, config));
+    errors.extend(check_agent_mode(mc, mode));
+    errors.extend(check_file_upload(mc));
+
+    errors
+}
+
+fn check_app_model(mc: &ModelConfig, config: &Config) -> Vec<LintError> {
+    let path = format!("{}/model", MODEL_CONFIG_PATH);
+
+    match &mc.model {
+        None => vec![LintError::with_hint(
+            &rules::APP_MISSING_MODEL,
+            "",
+            "model_config",
+            "App has no model configured",
+            "Select a model in the app's orchestration settings",
+        )
+        .at(MODEL_CONFIG_PATH)],
+        Some(model) if model.name.is_none() || model.provider.is_none() => {
+            vec![LintError::new(
+                &rules::APP_MISSING_MODEL,
+                "",
+                "model_config",
+                "App model is missing 'name' or 'provider'",
+            )
+            .at(path)]
+        }
+        Some(model) => check_model(model, "", "model_config", &path, config),
+    }
+}
+
+/// Prompt texts with their paths: pre_prompt for simple prompts, every message otherwise
+fn prompt_texts(mc: &ModelConfig) -> Vec<(String, &str)> {
+    if mc.prompt_type.as_deref() != Some("advanced") {
+        let text = mc.pre_prompt.as_deref().unwrap_or("");
+        return vec![(format!("{}/pre_prompt", MODEL_CONFIG_PATH), text)];
+    }
+
+    let mut texts = Vec::new();
+    if let Some(Value::Array(messages)) =
+        mc.chat_prompt_config.as_ref().and_then(|c| c.get("prompt"))
+    {
+        for (i, message) in messages.iter().enumerate() {
+            if let Some(text) = message.get("text").and_then(Value::as_str) {
+                let path = format!("{}/chat_prompt_config/prompt/{}/text", MODEL_CONFIG_PATH, i);
+                texts.push((path, text));
+            }
+        }
+    }
+    let completion = mc
+        .completion_prompt_config
+        .as_ref()
+        .and_then(|c| c.get("prompt"))
+        .and_then(|p| p.get("text"))
+        .and_then(Value::as_str);
+    if let Some(text) = completion {
+        let path = format!("{}/completion_prompt_config/prompt/text", MODEL_CONFIG_PATH);
+        texts.push((path, text));
+    }
+    texts
+}
+
+fn check_prompt(mc: &ModelConfig) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let texts = prompt_texts(mc);
+
+    if texts.iter().all(|(_, text)| text.trim().is_empty()) {
+        let path = texts
+            .first()
+            .map(|(p, _)| p.clone())
+            .unwrap_or_else(|| MODEL_CONFIG_PATH.to_string());
+        errors.push(
+            LintError::new(
+                &rules::APP_EMPTY_PROMPT,
+                "",
+                "model_config",
+                "App prompt is empty",
+            )
+            .at(path),
+        );
+    }
+
+    // {{name}} placeholders must be input fields; {{#context#}} and friends are built in
+    let fields: HashSet<&str> = form_fields(mc)
+        .filter_map(|(_, _, field)| field.variable.as_deref())
+        .collect();
+    let re = Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_]*)\}\}").unwrap();
+
+    for (path, text) in &texts {
+        let mut reported = HashSet::new();
+        for cap in re.captures_iter(text) {
+            let name = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            if !fields.contains(name) && reported.insert(name) {
+                errors.push(
+                    LintError::with_hint(
+                        &rules::APP_PROMPT_UNDEFINED_VARIABLE,
+                        "",
+                        "model_config",
+                        &format!("Prompt uses undefined variable: {{{{{}}}}}", name),
+                        &format!("Add an input field with variable '{}'", name),
+                    )
+                    .at(path.as_str()),
+                );
+            }
+        }
+    }
+
+    errors
+}
+
+/// Input fields as (index, field type, field)
+fn form_fields(mc: &ModelConfig) -> impl Iterator<Item = (usize, &str, &FormField)> {
+    mc.user_input_form
+        .iter()
+        .flatten()
+        .enumerate()
+        .flat_map(|(i, item)| {
+            item.iter()
+                .map(move |(kind, field)| (i, kind.as_str(), field))
+        })
+}
+
+fn check_input_form(mc: &ModelConfig) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (i, kind, field) in form_fields(mc) {
+        let path = format!("{}/user_input_form/{}/{}", MODEL_CONFIG_PATH, i, kind);
+        let label = field
+            .label
+            .as_deref()
+            .or(field.variable.as_deref())
+            .unwrap_or(kind);
+        let mut invalid = |message: String| {
+            errors.push(
+                LintError::new(&rules::APP_INPUT_FIELD_INVALID, "", label, &message)
+                    .at(path.as_str()),
+            );
+        };
+
+        match field.variable.as_deref() {
+            None | Some("") => invalid(format!("Input field {} has no 'variable'", i)),
+            Some(var) if !seen.insert(var) => {
+                invalid(format!("Duplicate input field variable: {}", var))
+            }
+            _ => {}
+        }
+        if field.label.as_deref().unwrap_or("").is_empty() {
+            invalid(format!("Input field {} has no 'label'", i));
+        }
+        if kind == "select" && field.options.as_ref().is_none_or(|o| o.is_empty()) {
+            invalid("Select input field has no options".to_string());
+        }
+    }
+
+    errors
+}
+
+fn check_datasets(mc: &ModelConfig, config: &Config) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let Some(known) = &config.knowledge.dataset_ids else {
+        return errors;
+    };
+
+    let datasets = mc
+        .dataset_configs
+        .as_ref()
+        .and_then(|d| d.datasets.as_ref())
+        .and_then(|d| d.datasets.as_ref());
+
+    for (i, entry) in datasets.into_iter().flatten().enumerate() {
+        let id = entry
+            .get("dataset")
+            .and_then(|d| d.get("id"))
+            .and_then(Value::as_str);
+        if let Some(id) = id {
+            if !known.iter().any(|k| k == id) {
+                errors.push(
+                    LintError::with_hint(
+                        &rules::KNOWLEDGE_UNKNOWN_DATASET,
+                        "",
+                        "model_config",
+                        &format!("Unknown dataset ID: {}", id),
+                        "Re-link the knowledge base or add the ID to knowledge.dataset_ids",
+                    )
+                    .at(format!(
+                        "{}/dataset_configs/datasets/datasets/{}/dataset/id",
+                        MODEL_CONFIG_PATH, i
+                    )),
+                );
+            }
+        }
+    }
+
+    errors
+}
+
+fn check_agent_mode(mc: &ModelConfig, mode: &str) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let path = format!("{}/agent_mode", MODEL_CONFIG_PATH);
+    let enabled = mc
+        .agent_mode
+        .as_ref()
+        .and_then(|a| a.enabled)
+        .unwrap_or(false);
+
+    if mode == "agent-chat" && !enabled {
+        errors.push(
+            LintError::with_hint(
+                &rules::AGENT_MODE_DISABLED,
+                "",
+                "model_config",
+                "Agent app does not have agent_mode enabled",
+                "Set agent_mode.enabled: true",
+            )
+            .at(path.as_str()),
+        );
+    }
+
+    let tools = mc.agent_mode.as_ref().and_then(|a| a.tools.as_ref());
+    for (i, tool) in tools.into_iter().flatten().enumerate() {
+        // Older exports list knowledge datasets among the tools
+        let is_dataset = tool.extra.contains_key("dataset")
+            || tool.extra.get("type").and_then(Value::as_str) == Some("dataset");
+        if is_dataset {
+            continue;
+        }
+
+        let missing: Vec<&str> = [
+            ("provider_id", &tool.provider_id),
+            ("tool_name", &tool.tool_name),
+        ]
+        .into_iter()
+        .filter(|(_, v)| v.as_deref().unwrap_or("").is_empty())
+        .map(|(k, _)| k)
+        .collect();
+
+        if !missing.is_empty() {
+            let name = tool
+                .tool_name
+                .as_deref()
+                .filter(|n| !n.is_empty())
+                .unwrap_or("tool");
+            errors.push(
+                LintError::new(
+                    &rules::AGENT_TOOL_INCOMPLETE,
+                    "",
+                    name,
+                    &format!("Agent tool {} missing '{}'", i, missing.join("', '")),
+                )
+                .at(format!("{}/tools/{}", path, i)),
+            );
+        }
+    }
+
+    errors
+}
+
+fn check_file_upload(mc: &ModelConfig) -> Vec<LintError> {
+    let Some(upload) = &mc.file_upload else {
+        return Vec::new();
+    };
+
+    let image_enabled = upload
+        .image
+        .as_ref()
+        .and_then(|i| i.get("enabled"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let has_types = upload
+        .allowed_file_types
+        .as_ref()
+        .is_some_and(|t| !t.is_empty());
+
+    if upload.enabled == Some(true) && !has_types && !image_enabled {
+        return vec![LintError::with_hint(
+            &rules::FILE_UPLOAD_NO_TYPES,
+            "",
+            "model_config",
+            "File upload is enabled but no file types are allowed",
+            "Set file_upload.allowed_file_types or enable image upload",
+        )
+        .at(format!("{}/file_upload", MODEL_CONFIG_PATH))];
+    }
+
+    Vec::new()
+}
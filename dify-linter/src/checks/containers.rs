@@ -0,0 +1,375 @@
+use crate::rules::{self, Rule};
+use crate::types::{LintContext, LintError, Node, EDGES_PATH};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A node type that runs a body of child nodes
+struct Container {
+    node_type: &'static str,
+    start_type: &'static str,
+    /// Field in a child's data naming its container
+    id_field: &'static str,
+    /// Boolean flag in a child's data marking it as inside a container
+    flag: &'static str,
+    unknown_parent: &'static Rule,
+    invalid_start: &'static Rule,
+    body_only: &'static Rule,
+}
+
+const CONTAINERS: &[Container] = &[
+    Container {
+        node_type: "iteration",
+        start_type: "iteration-start",
+        id_field: "iteration_id",
+        flag: "isInIteration",
+        unknown_parent: &rules::ITERATION_UNKNOWN_PARENT,
+        invalid_start: &rules::ITERATION_INVALID_START,
+        body_only: &rules::ITERATION_ITEM_OUTSIDE,
+    },
+    Container {
+        node_type: "loop",
+        start_type: "loop-start",
+        id_field: "loop_id",
+        flag: "isInLoop",
+        unknown_parent: &rules::LOOP_UNKNOWN_PARENT,
+        invalid_start: &rules::LOOP_INVALID_START,
+        body_only: &rules::LOOP_VARIABLE_OUTSIDE,
+    },
+];
+
+/// Check that container bodies are wired to their container and stay inside it
+pub fn check_containers(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let nodes: Vec<&Node> = nodes
+        .iter()
+        .filter(|n| n.id.is_some() && !n.is_note())
+        .collect();
+
+    let by_id: HashMap<&str, &Node> = nodes
+        .iter()
+        .filter_map(|n| Some((n.id.as_deref()?, *n)))
+        .collect();
+
+    for node in &nodes {
+        let id = node.id.as_deref().unwrap_or("");
+        let title = node_title(node);
+
+        for container in CONTAINERS {
+            errors.extend(check_parent(node, id, title, container, &by_id));
+        }
+        errors.extend(check_parent_id(node, id, title, &by_id));
+        errors.extend(check_loop_end(node, id, title, &by_id));
+
+        if let Some(container) = container_of_type(node.node_type()) {
+            errors.extend(check_start(node, id, title, container, &by_id));
+        }
+        if node.node_type() == "iteration" {
+            errors.extend(check_iteration_output(node, id, title, &by_id));
+        }
+
+        // Iteration item/index and loop variables are only visible to nodes in the body
+        for r in ctx.references(node) {
+            let Some(target) = by_id.get(r.node.as_str()) else {
+                continue;
+            };
+            let Some(container) = container_of_type(target.node_type()) else {
+                continue;
+            };
+            // A loop's own break conditions read its loop variables
+            let inside = r.node == id || is_inside(id, &r.node, &by_id);
+            if body_outputs(target).contains(&r.variable) && !inside {
+                let hint = match container.node_type {
+                    "iteration" => format!("Use [{}, output] after the iteration", r.node),
+                    _ => "Only reference loop variables from nodes inside the loop".to_string(),
+                };
+                errors.push(
+                    LintError::with_hint(
+                        container.body_only,
+                        id,
+                        title,
+                        &format!(
+                            "'{}.{}' is used outside the {}",
+                            r.node, r.variable, container.node_type
+                        ),
+                        &hint,
+                    )
+                    .at(&r.path),
+                );
+            }
+        }
+    }
+
+    errors.extend(check_edge_scopes(&by_id, ctx));
+
+    errors
+}
+
+fn container_of_type(node_type: &str) -> Option<&'static Container> {
+    CONTAINERS.iter().find(|c| c.node_type == node_type)
+}
+
+/// Outputs of a container that only exist while its body runs
+fn body_outputs(node: &Node) -> Vec<String> {
+    match node.node_type() {
+        "iteration" => vec!["item".to_string(), "index".to_string()],
+        "loop" => node
+            .data
+            .as_ref()
+            .and_then(|d| d.extra.get("loop_variables"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.get("label")?.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn node_title(node: &Node) -> &str {
+    node.data
+        .as_ref()
+        .and_then(|d| d.title.as_deref())
+        .unwrap_or(node.node_type())
+}
+
+/// The container ID field (`iteration_id`, `loop_id`) and `parentId` must agree and
+/// name an existing container of that type
+fn check_parent(
+    node: &Node,
+    id: &str,
+    title: &str,
+    container: &Container,
+    by_id: &HashMap<&str, &Node>,
+) -> Vec<LintError> {
+    let Some(data) = &node.data else {
+        return Vec::new();
+    };
+    let container_id = data.extra.get(container.id_field).and_then(Value::as_str);
+    let parent_id = node.extra.get("parentId").and_then(Value::as_str);
+    let flagged = data
+        .extra
+        .get(container.flag)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let error = |message: String, path: &str| {
+        vec![LintError::new(container.unknown_parent, id, title, &message).at(path)]
+    };
+
+    if let Some(container_id) = container_id {
+        if let Some(parent) = parent_id.filter(|p| *p != container_id) {
+            return error(
+                format!(
+                    "{} '{}' does not match parentId '{}'",
+                    container.id_field, container_id, parent
+                ),
+                container.id_field,
+            );
+        }
+        if by_id.get(container_id).map(|n| n.node_type()) != Some(container.node_type) {
+            return error(
+                format!(
+                    "{} '{}' does not match any {} node",
+                    container.id_field, container_id, container.node_type
+                ),
+                container.id_field,
+            );
+        }
+    } else if flagged && parent_id.is_none() {
+        return error(
+            format!(
+                "Node is marked {} but has no {}",
+                container.flag, container.id_field
+            ),
+            container.flag,
+        );
+    }
+
+    Vec::new()
+}
+
+/// A bare `parentId` must name an iteration or loop
+fn check_parent_id(
+    node: &Node,
+    id: &str,
+    title: &str,
+    by_id: &HashMap<&str, &Node>,
+) -> Vec<LintError> {
+    let Some(parent) = node.extra.get("parentId").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    // Mismatches with iteration_id/loop_id are reported by check_parent
+    let has_container_id = node
+        .data
+        .as_ref()
+        .is_some_and(|d| CONTAINERS.iter().any(|c| d.extra.contains_key(c.id_field)));
+    let is_container = by_id
+        .get(parent)
+        .is_some_and(|n| container_of_type(n.node_type()).is_some());
+
+    if has_container_id || is_container {
+        return Vec::new();
+    }
+    vec![LintError::new(
+        &rules::ITERATION_UNKNOWN_PARENT,
+        id,
+        title,
+        &format!("parentId '{}' is not an iteration or loop node", parent),
+    )]
+}
+
+/// `loop-end` nodes only make sense inside a loop
+fn check_loop_end(
+    node: &Node,
+    id: &str,
+    title: &str,
+    by_id: &HashMap<&str, &Node>,
+) -> Vec<LintError> {
+    if node.node_type() != "loop-end" {
+        return Vec::new();
+    }
+    let in_loop = node
+        .parent_id()
+        .and_then(|p| by_id.get(p))
+        .is_some_and(|p| p.node_type() == "loop");
+    if in_loop {
+        return Vec::new();
+    }
+    vec![LintError::with_hint(
+        &rules::LOOP_UNKNOWN_PARENT,
+        id,
+        title,
+        "loop-end node is not inside a loop",
+        "Move the node into a loop body or delete it",
+    )]
+}
+
+/// `start_node_id` must name the container's own start node
+fn check_start(
+    node: &Node,
+    id: &str,
+    title: &str,
+    container: &Container,
+    by_id: &HashMap<&str, &Node>,
+) -> Vec<LintError> {
+    let start = node
+        .data
+        .as_ref()
+        .and_then(|d| d.extra.get("start_node_id"))
+        .and_then(Value::as_str);
+    let Some(start) = start else {
+        return Vec::new();
+    };
+
+    let message = match by_id.get(start) {
+        None => format!("start_node_id '{}' not found", start),
+        Some(n) if n.node_type() != container.start_type => format!(
+            "start_node_id '{}' is a '{}' node, not '{}'",
+            start,
+            n.node_type(),
+            container.start_type
+        ),
+        Some(n) if n.parent_id() != Some(id) => format!(
+            "start_node_id '{}' is not inside this {}",
+            start, container.node_type
+        ),
+        Some(_) => return Vec::new(),
+    };
+
+    vec![LintError::with_hint(
+        container.invalid_start,
+        id,
+        title,
+        &message,
+        &format!(
+            "Point start_node_id at the {}'s {} child",
+            container.node_type, container.start_type
+        ),
+    )
+    .at("start_node_id")]
+}
+
+/// `output_selector` must read from a node in the iteration body
+fn check_iteration_output(
+    node: &Node,
+    id: &str,
+    title: &str,
+    by_id: &HashMap<&str, &Node>,
+) -> Vec<LintError> {
+    let source = node
+        .data
+        .as_ref()
+        .and_then(|d| d.extra.get("output_selector"))
+        .and_then(|s| s.get(0))
+        .and_then(Value::as_str);
+
+    match source {
+        Some(source) if by_id.contains_key(source) && !is_inside(source, id, by_id) => {
+            vec![LintError::with_hint(
+                &rules::ITERATION_OUTPUT_OUTSIDE,
+                id,
+                title,
+                &format!(
+                    "output_selector reads from '{}', outside the iteration",
+                    source
+                ),
+                "Select an output of a node inside the iteration",
+            )
+            .at("output_selector")]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Edges must connect nodes in the same container
+fn check_edge_scopes(by_id: &HashMap<&str, &Node>, ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    for (i, edge) in ctx.edges.iter().enumerate() {
+        let (Some(source), Some(target)) = (
+            edge.source.as_deref().and_then(|s| by_id.get(s)),
+            edge.target.as_deref().and_then(|t| by_id.get(t)),
+        ) else {
+            continue;
+        };
+
+        if source.parent_id() != target.parent_id() {
+            let scope = |n: &Node| match n.parent_id() {
+                Some(parent) => format!("inside '{}'", parent),
+                None => "at the top level".to_string(),
+            };
+            errors.push(
+                LintError::with_hint(
+                    &rules::EDGE_CROSSES_CONTAINER,
+                    edge.id.as_deref().unwrap_or(""),
+                    "edge",
+                    &format!(
+                        "Edge connects '{}' ({}) to '{}' ({})",
+                        node_title(source),
+                        scope(source),
+                        node_title(target),
+                        scope(target)
+                    ),
+                    "Connect to or from the container node instead",
+                )
+                .at(format!("{}/{}", EDGES_PATH, i)),
+            );
+        }
+    }
+
+    errors
+}
+
+/// Whether `id` is nested (at any depth) inside the container `container`
+fn is_inside(id: &str, container: &str, by_id: &HashMap<&str, &Node>) -> bool {
+    let mut current = id;
+    // Bounded walk, in case parent links form a cycle
+    for _ in 0..by_id.len() {
+        match by_id.get(current).and_then(|n| n.parent_id()) {
+            Some(parent) if parent == container => return true,
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
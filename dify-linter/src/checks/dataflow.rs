@@ -0,0 +1,115 @@
+use crate::checks::graph::Flow;
+use crate::checks::references::Target;
+use crate::rules;
+use crate::types::{LintContext, LintError, Node};
+use std::collections::HashSet;
+
+/// Check that every referenced output variable exists on the referenced node
+pub fn check_output_references(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        let Some(data) = &node.data else { continue };
+        let node_id = node.id.as_deref().unwrap_or("");
+        let node_title = data.title.as_deref().unwrap_or(node.node_type());
+
+        let mut seen = HashSet::new();
+        for r in ctx.references(node) {
+            if !seen.insert((r.path.clone(), r.node.clone(), r.variable.clone())) {
+                continue;
+            }
+            if let Target::UnknownOutput(ref_node, outputs) = r.resolve(ctx) {
+                errors.push(r.unknown_output_error(ref_node, &outputs, node_id, node_title));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Check that every referenced node has run by the time the referencing node runs
+pub fn check_reference_order(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let flow = Flow::new(nodes, &ctx.edges);
+
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        // End outputs are checked by check_end_nodes; aggregators merge branches on purpose
+        if matches!(node.node_type(), "end" | "variable-aggregator") {
+            continue;
+        }
+        let Some(data) = &node.data else { continue };
+        let Some(node_id) = node.id.as_deref().filter(|id| flow.is_reachable(id)) else {
+            continue;
+        };
+        let node_title = data.title.as_deref().unwrap_or(node.node_type());
+        let own_containers = containers(node, ctx);
+
+        let mut seen = HashSet::new();
+        for r in ctx.references(node) {
+            if !seen.insert((r.path.clone(), r.node.clone())) {
+                continue;
+            }
+            let (Target::Output(ref_node) | Target::UnknownOutput(ref_node, _)) = r.resolve(ctx)
+            else {
+                continue;
+            };
+            // A container reads its own body, and nodes outside see the body as the container
+            let ref_containers = containers(ref_node, ctx);
+            if ref_containers.contains(&node_id) {
+                continue;
+            }
+            let source = ref_containers
+                .iter()
+                .rfind(|c| !own_containers.contains(c))
+                .copied()
+                .unwrap_or(&r.node);
+            if source == node_id || flow.always_before(source, node_id) {
+                continue;
+            }
+
+            let (rule, when, hint) = if flow.sometimes_before(source, node_id) {
+                (
+                    &rules::REFERENCE_SOME_PATHS,
+                    "only runs on some paths into",
+                    "Merge the branches with a Variable Aggregator and reference its output",
+                )
+            } else {
+                (
+                    &rules::REFERENCE_NOT_RUN,
+                    "does not run before",
+                    "Reference a node that runs before this one, or connect it upstream",
+                )
+            };
+            errors.push(
+                LintError::with_hint(
+                    rule,
+                    node_id,
+                    node_title,
+                    &format!(
+                        "References '{}.{}', which {} this node",
+                        r.node, r.variable, when
+                    ),
+                    hint,
+                )
+                .at(&r.path),
+            );
+        }
+    }
+
+    errors
+}
+
+/// IDs of the iterations and loops holding a node, innermost first
+fn containers<'a>(node: &'a Node, ctx: &'a LintContext) -> Vec<&'a str> {
+    let mut chain = Vec::new();
+    let mut current = node;
+    // Bounded walk, in case parent links form a cycle
+    while chain.len() < ctx.node_map.len() {
+        let Some(parent) = current.parent_id().and_then(|p| ctx.get_node(p)) else {
+            break;
+        };
+        chain.push(parent.id.as_deref().unwrap_or(""));
+        current = parent;
+    }
+    chain
+}
@@ -1,32 +1,123 @@
-use crate::types::{Edge, LintContext, LintError};
+use crate::rules;
+use crate::types::{Edge, LintContext, LintError, Node, EDGES_PATH};
 
 /// Check edge connections
 pub fn check_edges(edges: &[Edge], ctx: &LintContext) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    for edge in edges {
+    for (i, edge) in edges.iter().enumerate() {
         let edge_id = edge.id.as_deref().unwrap_or("");
+        let edge_path = format!("{}/{}", EDGES_PATH, i);
 
         if let Some(source) = &edge.source {
             if !ctx.node_exists(source) {
-                errors.push(LintError::error(
-                    edge_id,
-                    "edge",
-                    &format!("Edge source '{}' not found", source),
-                ));
+                errors.push(
+                    LintError::new(
+                        &rules::EDGE_SOURCE_MISSING,
+                        edge_id,
+                        "edge",
+                        &format!("Edge source '{}' not found", source),
+                    )
+                    .at(format!("{}/source", edge_path)),
+                );
+            }
+        }
+
+        if let Some(source_node) = edge.source.as_deref().and_then(|s| ctx.get_node(s)) {
+            let handle = edge.source_handle.as_deref().unwrap_or("source");
+            if let Some(message) = check_source_handle(source_node, handle) {
+                errors.push(
+                    LintError::with_hint(
+                        &rules::EDGE_INVALID_SOURCE_HANDLE,
+                        edge_id,
+                        "edge",
+                        &message,
+                        &format!("Use one of: {}", source_handles(source_node).join(", ")),
+                    )
+                    .at(format!("{}/sourceHandle", edge_path)),
+                );
             }
         }
 
         if let Some(target) = &edge.target {
             if !ctx.node_exists(target) {
-                errors.push(LintError::error(
-                    edge_id,
-                    "edge",
-                    &format!("Edge target '{}' not found", target),
-                ));
+                errors.push(
+                    LintError::new(
+                        &rules::EDGE_TARGET_MISSING,
+                        edge_id,
+                        "edge",
+                        &format!("Edge target '{}' not found", target),
+                    )
+                    .at(format!("{}/target", edge_path)),
+                );
             }
         }
     }
 
     errors
 }
+
+/// Handles an edge may leave `node` from
+fn source_handles(node: &Node) -> Vec<String> {
+    let Some(data) = &node.data else {
+        return vec!["source".to_string()];
+    };
+
+    let mut handles: Vec<String> = match node.node_type() {
+        "if-else" => match &data.cases {
+            Some(cases) if !cases.is_empty() => cases
+                .iter()
+                .filter_map(|c| c.case_id.clone())
+                .chain(["false".to_string()])
+                .collect(),
+            // Legacy format without cases
+            _ => vec!["true".to_string(), "false".to_string()],
+        },
+        "question-classifier" => data
+            .classes
+            .iter()
+            .flatten()
+            .filter_map(|c| c.id.clone())
+            .collect(),
+        _ => vec!["source".to_string()],
+    };
+
+    if data.extra.get("error_strategy").and_then(|v| v.as_str()) == Some("fail-branch") {
+        handles.push("fail-branch".to_string());
+    }
+
+    handles
+}
+
+/// Why `handle` is not a valid branch of `node`, if it is not
+fn check_source_handle(node: &Node, handle: &str) -> Option<String> {
+    let handles = source_handles(node);
+    if handles.iter().any(|h| h == handle) {
+        return None;
+    }
+
+    let title = node
+        .data
+        .as_ref()
+        .and_then(|d| d.title.as_deref())
+        .unwrap_or(node.node_type());
+
+    Some(match node.node_type() {
+        _ if handle == "fail-branch" => format!(
+            "Edge uses 'fail-branch' but node '{}' does not have error_strategy: fail-branch",
+            title
+        ),
+        "if-else" => format!(
+            "Edge sourceHandle '{}' is not a case_id or 'false' of IF/ELSE node '{}'",
+            handle, title
+        ),
+        "question-classifier" => format!(
+            "Edge sourceHandle '{}' is not a class id of Question Classifier '{}'",
+            handle, title
+        ),
+        _ => format!(
+            "Edge sourceHandle '{}' is invalid for node '{}'; expected 'source'",
+            handle, title
+        ),
+    })
+}
@@ -0,0 +1,161 @@
+use crate::checks::graph::Flow;
+use crate::rules;
+use crate::types::{LintContext, LintError, Node, VARIABLE_SCOPES};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+
+/// Check End node outputs and that parallel branches do not finish at different End nodes
+pub fn check_end_nodes(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let flow = Flow::new(nodes, &ctx.edges);
+
+    for node in nodes.iter().filter(|n| n.node_type() == "end") {
+        let Some(data) = &node.data else { continue };
+        let id = node.id.as_deref().unwrap_or("");
+        let title = data.title.as_deref().unwrap_or("end");
+        let mut declared: Vec<&str> = Vec::new();
+
+        let outputs = data.extra.get("outputs").and_then(Value::as_array);
+        for (i, output) in outputs.into_iter().flatten().enumerate() {
+            let path = format!("outputs/{}", i);
+            let name = output.get("variable").and_then(Value::as_str).unwrap_or("");
+            let selector: Vec<&str> = output
+                .get("value_selector")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+
+            let message = if name.is_empty() {
+                Some(format!("End output {} has no 'variable' name", i))
+            } else if declared.contains(&name) {
+                Some(format!("Duplicate End output: {}", name))
+            } else if selector.len() < 2 {
+                Some(format!("End output '{}' has no 'value_selector'", name))
+            } else {
+                None
+            };
+            if !name.is_empty() && !declared.contains(&name) {
+                declared.push(name);
+            }
+            if let Some(message) = message {
+                errors
+                    .push(LintError::new(&rules::END_OUTPUT_INVALID, id, title, &message).at(path));
+                continue;
+            }
+
+            // Missing nodes are reported as reference-node-missing
+            let source = selector[0];
+            if VARIABLE_SCOPES.contains(&source)
+                || !ctx.node_exists(source)
+                || !flow.is_reachable(id)
+                || flow.always_before(source, id)
+            {
+                continue;
+            }
+            let when = if flow.sometimes_before(source, id) {
+                "only runs on some paths into"
+            } else {
+                "does not run before"
+            };
+            errors.push(
+                LintError::with_hint(
+                    &rules::END_OUTPUT_UNAVAILABLE,
+                    id,
+                    title,
+                    &format!(
+                        "End output '{}' reads '{}', which {} this End node",
+                        name,
+                        selector.join("."),
+                        when
+                    ),
+                    "Select an output of a node on every path into this End node, or merge \
+                     the branches with a Variable Aggregator",
+                )
+                .at(format!("{}/value_selector", path)),
+            );
+        }
+    }
+
+    errors.extend(check_parallel_ends(nodes, ctx, &flow));
+
+    errors
+}
+
+/// Branches that run in parallel race to their End nodes, and the first one to finish wins
+fn check_parallel_ends(nodes: &[Node], ctx: &LintContext, flow: &Flow) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let ends: HashSet<&str> = nodes
+        .iter()
+        .filter(|n| n.node_type() == "end")
+        .filter_map(|n| n.id.as_deref())
+        .collect();
+    if ends.len() < 2 {
+        return errors;
+    }
+
+    // Edges leaving the same handle run in parallel; different handles are exclusive branches
+    let mut forks: BTreeMap<(&str, &str), Vec<&str>> = BTreeMap::new();
+    for edge in &ctx.edges {
+        if let (Some(source), Some(target)) = (edge.source.as_deref(), edge.target.as_deref()) {
+            let handle = edge.source_handle.as_deref().unwrap_or("source");
+            let targets = forks.entry((source, handle)).or_default();
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+
+    let mut reported = HashSet::new();
+    for ((source, _), targets) in forks.iter().filter(|(_, t)| t.len() > 1) {
+        let branch_ends: Vec<HashSet<&str>> = targets
+            .iter()
+            .map(|t| flow.downstream(t).intersection(&ends).copied().collect())
+            .collect();
+
+        // Two branches race when they can finish at different End nodes
+        let mut racing: Vec<&str> = Vec::new();
+        for (i, a) in branch_ends.iter().enumerate() {
+            for b in branch_ends[i + 1..]
+                .iter()
+                .filter(|b| !a.is_empty() && !b.is_empty())
+            {
+                if a.union(b).count() > 1 {
+                    racing.extend(a.union(b));
+                }
+            }
+        }
+        racing.sort();
+        racing.dedup();
+        if racing.is_empty() || !reported.insert(racing.clone()) {
+            continue;
+        }
+
+        let titles: Vec<String> = racing
+            .iter()
+            .filter_map(|id| ctx.get_node(id))
+            .map(|n| {
+                let title = n.data.as_ref().and_then(|d| d.title.as_deref());
+                format!("'{}'", title.unwrap_or(n.node_type()))
+            })
+            .collect();
+        let source_title = ctx
+            .get_node(source)
+            .and_then(|n| n.data.as_ref())
+            .and_then(|d| d.title.as_deref())
+            .unwrap_or(source);
+        errors.push(LintError::with_hint(
+            &rules::PARALLEL_END_NODES,
+            source,
+            source_title,
+            &format!(
+                "Parallel branches from this node finish at different End nodes: {}",
+                titles.join(", ")
+            ),
+            "Join the branches with a Variable Aggregator before a single End node",
+        ));
+    }
+
+    errors
+}
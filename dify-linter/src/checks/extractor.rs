@@ -0,0 +1,167 @@
+use crate::rules;
+use crate::schema::IDENTIFIER_RE;
+use crate::types::{LintError, NodeData};
+use serde_json::Value;
+
+/// Parameter types a parameter-extractor can extract
+const PARAMETER_TYPES: &[&str] = &[
+    "string",
+    "number",
+    "bool",
+    "select",
+    "array[string]",
+    "array[number]",
+    "array[object]",
+    "array[bool]",
+];
+
+/// How a parameter-extractor asks the model for parameters
+const REASONING_MODES: &[&str] = &["function_call", "prompt"];
+
+/// Check Parameter Extractor node configuration
+pub fn check_parameter_extractor_node(
+    node_id: &str,
+    node_title: &str,
+    data: &NodeData,
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    let model_message = match &data.model {
+        None => Some("Parameter extractor missing 'model' configuration"),
+        Some(m) if m.name.as_deref().unwrap_or("").is_empty() => {
+            Some("Parameter extractor model missing 'name'")
+        }
+        Some(m) if m.provider.as_deref().unwrap_or("").is_empty() => {
+            Some("Parameter extractor model missing 'provider'")
+        }
+        Some(_) => None,
+    };
+    if let Some(message) = model_message {
+        errors.push(
+            LintError::new(
+                &rules::EXTRACTOR_MISSING_MODEL,
+                node_id,
+                node_title,
+                message,
+            )
+            .at("model"),
+        );
+    }
+
+    errors.extend(check_query(node_id, node_title, data));
+    errors.extend(check_parameters(node_id, node_title, data));
+
+    if let Some(mode) = data.extra.get("reasoning_mode").filter(|m| !m.is_null()) {
+        let name = mode
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or(mode.to_string());
+        if !REASONING_MODES.contains(&name.as_str()) {
+            errors.push(
+                LintError::with_hint(
+                    &rules::EXTRACTOR_INVALID_REASONING_MODE,
+                    node_id,
+                    node_title,
+                    &format!("Unknown reasoning_mode: {}", name),
+                    &format!("Use one of: {}", REASONING_MODES.join(", ")),
+                )
+                .at("reasoning_mode"),
+            );
+        }
+    }
+
+    errors
+}
+
+/// `query` selects the text to extract parameters from
+fn check_query(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let query = data.extra.get("query").and_then(Value::as_array);
+
+    // The selected node and output are checked with every other reference
+    if query.is_some_and(|q| !q.is_empty()) {
+        return Vec::new();
+    }
+    vec![LintError::with_hint(
+        &rules::EXTRACTOR_MISSING_QUERY,
+        node_id,
+        node_title,
+        "Parameter extractor has no 'query' input",
+        "Select the variable to extract parameters from, e.g. sys.query",
+    )
+    .at("query")]
+}
+
+/// Parameters need unique identifier names, a known type, and options when selectable
+fn check_parameters(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let mut declared: Vec<&str> = Vec::new();
+
+    let parameters = data.extra.get("parameters").and_then(Value::as_array);
+    if parameters.is_none_or(|p| p.is_empty()) {
+        errors.push(
+            LintError::new(
+                &rules::EXTRACTOR_INVALID_PARAMETER,
+                node_id,
+                node_title,
+                "Parameter extractor has no parameters",
+            )
+            .at("parameters"),
+        );
+        return errors;
+    }
+
+    for (i, param) in parameters.into_iter().flatten().enumerate() {
+        let name = param.get("name").and_then(Value::as_str).unwrap_or("");
+        let param_type = param.get("type").and_then(Value::as_str).unwrap_or("");
+        let has_options = param
+            .get("options")
+            .and_then(Value::as_array)
+            .is_some_and(|o| o.iter().any(|v| v.as_str().is_some_and(|s| !s.is_empty())));
+
+        let message = if name.is_empty() {
+            Some(format!("Parameter {} has no 'name'", i))
+        } else if declared.contains(&name) {
+            Some(format!("Duplicate parameter: {}", name))
+        } else if !IDENTIFIER_RE.is_match(name) {
+            Some(format!(
+                "Parameter name '{}' must start with a letter or underscore and contain only \
+                 letters, digits and underscores",
+                name
+            ))
+        } else if name.starts_with("__") {
+            Some(format!(
+                "Parameter name '{}' clashes with the node's built-in outputs",
+                name
+            ))
+        } else if !PARAMETER_TYPES.contains(&param_type) {
+            Some(format!(
+                "Parameter '{}' has unknown type '{}'",
+                name, param_type
+            ))
+        } else if param_type == "select" && !has_options {
+            Some(format!("Select parameter '{}' has no options", name))
+        } else if param.get("required").is_some_and(|r| !r.is_boolean()) {
+            Some(format!("Parameter '{}' has a non-boolean 'required'", name))
+        } else {
+            None
+        };
+        if !name.is_empty() && !declared.contains(&name) {
+            declared.push(name);
+        }
+
+        if let Some(message) = message {
+            let mut err = LintError::new(
+                &rules::EXTRACTOR_INVALID_PARAMETER,
+                node_id,
+                node_title,
+                &message,
+            );
+            if !param_type.is_empty() && !PARAMETER_TYPES.contains(&param_type) {
+                err.fix_hint = Some(format!("Use one of: {}", PARAMETER_TYPES.join(", ")));
+            }
+            errors.push(err.at(format!("parameters/{}", i)));
+        }
+    }
+
+    errors
+}
@@ -0,0 +1,452 @@
+use crate::rules;
+use crate::types::{Edge, LintContext, LintError, Node};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Node types that finish a workflow run
+const TERMINAL_TYPES: &[&str] = &["end", "answer"];
+
+/// Entry nodes of iteration and loop containers, connected only to their children
+const CONTAINER_START_TYPES: &[&str] = &["iteration-start", "loop-start"];
+
+/// Check that every node is connected, reachable from an entry node, and leads somewhere
+pub fn check_graph(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    // Sticky notes live on the canvas but are not part of the graph
+    let nodes: Vec<&Node> = nodes
+        .iter()
+        .filter(|n| n.id.is_some() && !n.is_note())
+        .collect();
+
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut has_incoming: HashSet<&str> = HashSet::new();
+    for edge in &ctx.edges {
+        if let (Some(source), Some(target)) = (edge.source.as_deref(), edge.target.as_deref()) {
+            if ctx.node_exists(source) && ctx.node_exists(target) {
+                successors.entry(source).or_default().push(target);
+                has_incoming.insert(target);
+            }
+        }
+    }
+
+    // A container runs its children, so they are reachable through it
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in &nodes {
+        if let (Some(id), Some(parent)) = (node.id.as_deref(), node.parent_id()) {
+            children.entry(parent).or_default().push(id);
+        }
+    }
+
+    errors.extend(check_cycles(&nodes, &successors));
+
+    let entries: Vec<&str> = nodes
+        .iter()
+        .filter(|n| is_entry(n.node_type()))
+        .filter_map(|n| n.id.as_deref())
+        .collect();
+    let reachable = reachable_from(&entries, &successors, &children);
+
+    for node in &nodes {
+        let id = node.id.as_deref().unwrap_or("");
+        let node_type = node.node_type();
+        let title = node
+            .data
+            .as_ref()
+            .and_then(|d| d.title.as_deref())
+            .unwrap_or(node_type);
+
+        if CONTAINER_START_TYPES.contains(&node_type) {
+            continue;
+        }
+
+        let has_outgoing = successors.contains_key(id);
+        if !has_outgoing && !has_incoming.contains(id) && nodes.len() > 1 {
+            errors.push(LintError::with_hint(
+                &rules::ORPHAN_NODE,
+                id,
+                title,
+                "Node is not connected to any other node",
+                "Connect the node to the workflow or delete it",
+            ));
+            continue;
+        }
+
+        if !entries.is_empty() && !reachable.contains(id) {
+            errors.push(LintError::with_hint(
+                &rules::UNREACHABLE_NODE,
+                id,
+                title,
+                "Node is not reachable from the start node",
+                "Add an edge from an upstream node or delete the node",
+            ));
+        }
+
+        if !has_outgoing && node.parent_id().is_none() && !TERMINAL_TYPES.contains(&node_type) {
+            errors.push(LintError::with_hint(
+                &rules::DEAD_END_NODE,
+                id,
+                title,
+                "Branch ends at this node without reaching an End or Answer node",
+                "Connect the node to a downstream node, End or Answer",
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Report every strongly connected component, following edges within one container scope
+fn check_cycles(nodes: &[&Node], successors: &HashMap<&str, Vec<&str>>) -> Vec<LintError> {
+    let scope: HashMap<&str, Option<&str>> = nodes
+        .iter()
+        .filter_map(|n| Some((n.id.as_deref()?, n.parent_id())))
+        .collect();
+    let titles: HashMap<&str, &str> = nodes
+        .iter()
+        .filter_map(|n| Some((n.id.as_deref()?, n.data.as_ref()?.title.as_deref()?)))
+        .collect();
+
+    // Edges leaving a container's scope belong to a different subgraph
+    let scoped: HashMap<&str, Vec<&str>> = successors
+        .iter()
+        .map(|(&source, targets)| {
+            let targets = targets
+                .iter()
+                .copied()
+                .filter(|t| scope.get(t) == scope.get(source))
+                .collect();
+            (source, targets)
+        })
+        .collect();
+
+    let mut tarjan = Tarjan::new(&scoped);
+    for id in nodes.iter().filter_map(|n| n.id.as_deref()) {
+        if !tarjan.index.contains_key(id) {
+            tarjan.visit(id);
+        }
+    }
+
+    let mut components = tarjan.components;
+    components.retain(|c| c.len() > 1 || scoped.get(c[0]).is_some_and(|t| t.contains(&c[0])));
+
+    // Report in document order, starting each cycle from its first node in the file
+    let position: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, n)| Some((n.id.as_deref()?, i)))
+        .collect();
+    components.sort_by_key(|c| c.iter().map(|id| position[id]).min());
+
+    components
+        .iter()
+        .map(|component| {
+            let first = *component.iter().min_by_key(|id| position[*id]).unwrap();
+            let order = cycle_order(first, component, &scoped);
+            let label = |id: &str| match titles.get(id) {
+                Some(title) => format!("{} [{}]", title, id),
+                None => format!("[{}]", id),
+            };
+            let path: Vec<String> = order.iter().chain([&first]).map(|id| label(id)).collect();
+
+            LintError::with_hint(
+                &rules::GRAPH_CYCLE,
+                first,
+                titles.get(first).copied().unwrap_or(""),
+                &format!("Nodes form a cycle: {}", path.join(" -> ")),
+                "Remove the back edge; use an Iteration or Loop node to repeat steps",
+            )
+        })
+        .collect()
+}
+
+/// Nodes of a component in depth-first order from `start`, following edges
+fn cycle_order<'a>(
+    start: &'a str,
+    component: &[&'a str],
+    successors: &HashMap<&'a str, Vec<&'a str>>,
+) -> Vec<&'a str> {
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    let mut seen = HashSet::new();
+
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        order.push(id);
+        let next = successors.get(id).map(Vec::as_slice).unwrap_or_default();
+        for &target in next.iter().rev() {
+            if component.contains(&target) && !seen.contains(target) {
+                stack.push(target);
+            }
+        }
+    }
+
+    order
+}
+
+/// Tarjan's strongly connected components algorithm
+struct Tarjan<'a, 'g> {
+    successors: &'g HashMap<&'a str, Vec<&'a str>>,
+    index: HashMap<&'a str, usize>,
+    lowlink: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a, 'g> Tarjan<'a, 'g> {
+    fn new(successors: &'g HashMap<&'a str, Vec<&'a str>>) -> Self {
+        Self {
+            successors,
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, id: &'a str) {
+        let index = self.index.len();
+        self.index.insert(id, index);
+        self.lowlink.insert(id, index);
+        self.stack.push(id);
+        self.on_stack.insert(id);
+
+        let successors = self.successors;
+        for &target in successors.get(id).into_iter().flatten() {
+            if !self.index.contains_key(target) {
+                self.visit(target);
+                let low = self.lowlink[id].min(self.lowlink[target]);
+                self.lowlink.insert(id, low);
+            } else if self.on_stack.contains(target) {
+                let low = self.lowlink[id].min(self.index[target]);
+                self.lowlink.insert(id, low);
+            }
+        }
+
+        if self.lowlink[id] == index {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member);
+                if member == id {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// A branch taken out of a node: its ID and the source handle of the edge
+type Branch<'a> = (&'a str, &'a str);
+
+/// Which nodes run before which: a node runs after its predecessors along edges, and a
+/// container's start node runs after the container
+pub(crate) struct Flow<'a> {
+    successors: HashMap<&'a str, Vec<&'a str>>,
+    /// Predecessors with the handle of the edge they are connected by
+    incoming: HashMap<&'a str, Vec<Branch<'a>>>,
+    /// Nodes that have run whenever the key runs, including the key itself
+    must_run: HashMap<&'a str, HashSet<&'a str>>,
+}
+
+impl<'a> Flow<'a> {
+    pub fn new(nodes: &'a [Node], edges: &'a [Edge]) -> Self {
+        let nodes: Vec<&Node> = nodes
+            .iter()
+            .filter(|n| n.id.is_some() && !n.is_note())
+            .collect();
+        let ids: HashSet<&str> = nodes.iter().filter_map(|n| n.id.as_deref()).collect();
+
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut incoming: HashMap<&str, Vec<Branch>> = HashMap::new();
+        let mut link = |source: &'a str, target: &'a str, handle: &'a str| {
+            successors.entry(source).or_default().push(target);
+            incoming.entry(target).or_default().push((source, handle));
+        };
+        for edge in edges {
+            if let (Some(source), Some(target)) = (edge.source.as_deref(), edge.target.as_deref()) {
+                if ids.contains(source) && ids.contains(target) {
+                    link(
+                        source,
+                        target,
+                        edge.source_handle.as_deref().unwrap_or("source"),
+                    );
+                }
+            }
+        }
+        for node in &nodes {
+            if CONTAINER_START_TYPES.contains(&node.node_type()) {
+                if let (Some(id), Some(parent)) = (node.id.as_deref(), node.parent_id()) {
+                    if ids.contains(parent) {
+                        link(parent, id, "source");
+                    }
+                }
+            }
+        }
+
+        let entries: Vec<&str> = nodes
+            .iter()
+            .filter(|n| is_entry(n.node_type()))
+            .filter_map(|n| n.id.as_deref())
+            .collect();
+        let must_run = must_run(&entries, &successors, &incoming);
+
+        Self {
+            successors,
+            incoming,
+            must_run,
+        }
+    }
+
+    /// Whether the node can run at all
+    pub fn is_reachable(&self, id: &str) -> bool {
+        self.must_run.contains_key(id)
+    }
+
+    /// Whether `before` has run whenever `node` runs
+    pub fn always_before(&self, before: &str, node: &str) -> bool {
+        before != node && self.must_run.get(node).is_some_and(|d| d.contains(before))
+    }
+
+    /// Whether `before` runs before `node` on at least one path
+    pub fn sometimes_before(&self, before: &str, node: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![node];
+        while let Some(id) = stack.pop() {
+            for &(pred, _) in self.incoming.get(id).into_iter().flatten() {
+                if pred == before {
+                    return true;
+                }
+                if seen.insert(pred) {
+                    stack.push(pred);
+                }
+            }
+        }
+        false
+    }
+
+    /// Nodes reachable from `id` along edges, including `id`
+    pub fn downstream(&self, id: &'a str) -> HashSet<&'a str> {
+        reachable_from(&[id], &self.successors, &HashMap::new())
+    }
+}
+
+/// Nodes that have run whenever each node reachable from the entries runs. Dify runs a node
+/// once every incoming edge has been taken or skipped: edges taken under the same branch
+/// decisions all run first, e.g. parallel branches joining, while edges behind different
+/// IF/ELSE, classifier or fail branches are alternatives.
+fn must_run<'a>(
+    entries: &[&'a str],
+    successors: &HashMap<&'a str, Vec<&'a str>>,
+    incoming: &HashMap<&'a str, Vec<Branch<'a>>>,
+) -> HashMap<&'a str, HashSet<&'a str>> {
+    // Breadth-first order, so leftovers on cycles are settled in a stable order
+    let mut breadth_first: Vec<&str> = Vec::new();
+    let mut seen: HashSet<&str> = entries.iter().copied().collect();
+    let mut queue: VecDeque<&str> = entries.iter().copied().collect();
+    while let Some(id) = queue.pop_front() {
+        breadth_first.push(id);
+        for &target in successors.get(id).into_iter().flatten() {
+            if seen.insert(target) {
+                queue.push_back(target);
+            }
+        }
+    }
+
+    // Topological order, so every predecessor outside a cycle is settled first
+    let mut pending: HashMap<&str, usize> = breadth_first
+        .iter()
+        .map(|&id| {
+            let preds = incoming.get(id).into_iter().flatten();
+            (id, preds.filter(|(p, _)| seen.contains(p)).count())
+        })
+        .collect();
+    let mut order: Vec<&str> = Vec::new();
+    let mut queue: VecDeque<&str> = entries.iter().copied().collect();
+    while let Some(id) = queue.pop_front() {
+        order.push(id);
+        for &target in successors.get(id).into_iter().flatten() {
+            let count = pending.entry(target).or_default();
+            *count = count.saturating_sub(1);
+            if *count == 0 && !entries.contains(&target) {
+                queue.push_back(target);
+            }
+        }
+    }
+    let ordered: HashSet<&str> = order.iter().copied().collect();
+    let leftovers: Vec<&str> = breadth_first
+        .into_iter()
+        .filter(|id| !ordered.contains(id))
+        .collect();
+    order.extend(leftovers);
+
+    let mut ran: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut taken: HashMap<&str, BTreeSet<Branch>> = HashMap::new();
+    for id in order {
+        if entries.contains(&id) {
+            ran.insert(id, HashSet::from([id]));
+            taken.insert(id, BTreeSet::new());
+            continue;
+        }
+
+        // Incoming edges grouped by the branches that must be taken to reach them
+        let mut groups: Vec<(BTreeSet<Branch>, HashSet<&str>)> = Vec::new();
+        for &(source, handle) in incoming.get(id).into_iter().flatten() {
+            let (Some(before), Some(branches)) = (ran.get(source), taken.get(source)) else {
+                continue;
+            };
+            let mut branches = branches.clone();
+            if handle != "source" {
+                branches.insert((source, handle));
+            }
+            match groups.iter_mut().find(|(b, _)| *b == branches) {
+                Some((_, group)) => group.extend(before.iter().copied()),
+                None => groups.push((branches, before.clone())),
+            }
+        }
+
+        let Some((branches, mut group)) = groups.into_iter().reduce(|(b1, g1), (b2, g2)| {
+            (
+                b1.intersection(&b2).copied().collect(),
+                g1.intersection(&g2).copied().collect(),
+            )
+        }) else {
+            continue;
+        };
+        group.insert(id);
+        ran.insert(id, group);
+        taken.insert(id, branches);
+    }
+
+    ran
+}
+
+/// Start node or any trigger (`trigger-schedule`, `trigger-webhook`, ...)
+pub(crate) fn is_entry(node_type: &str) -> bool {
+    node_type == "start" || node_type.starts_with("trigger-")
+}
+
+/// Breadth-first search along edges and into containers
+fn reachable_from<'a>(
+    entries: &[&'a str],
+    successors: &HashMap<&'a str, Vec<&'a str>>,
+    children: &HashMap<&'a str, Vec<&'a str>>,
+) -> HashSet<&'a str> {
+    let mut seen: HashSet<&str> = entries.iter().copied().collect();
+    let mut queue: VecDeque<&str> = entries.iter().copied().collect();
+
+    while let Some(id) = queue.pop_front() {
+        let next = successors.get(id).into_iter().chain(children.get(id));
+        for &target in next.flatten() {
+            if seen.insert(target) {
+                queue.push_back(target);
+            }
+        }
+    }
+
+    seen
+}
@@ -0,0 +1,282 @@
+use crate::checks::references::TEMPLATE_RE;
+use crate::rules;
+use crate::types::{LintError, NodeData};
+use serde_json::Value;
+use url::Url;
+
+/// Methods an http-request node can send
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// Body types an http-request node can send
+const BODY_TYPES: &[&str] = &[
+    "none",
+    "form-data",
+    "x-www-form-urlencoded",
+    "raw-text",
+    "json",
+    "binary",
+];
+
+/// Dify's default HTTP_REQUEST_MAX_{CONNECT,READ,WRITE}_TIMEOUT, in seconds
+const DEFAULT_MAX_TIMEOUTS: [(&str, u64); 3] = [("connect", 300), ("read", 600), ("write", 600)];
+
+/// Retry ranges the Dify editor allows
+const MAX_RETRIES: std::ops::RangeInclusive<u64> = 1..=10;
+const RETRY_INTERVAL_MS: std::ops::RangeInclusive<u64> = 100..=5000;
+
+/// Check HTTP Request node configuration
+pub fn check_http_request_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    errors.extend(check_method(node_id, node_title, data));
+    errors.extend(check_url(node_id, node_title, data));
+    errors.extend(check_body(node_id, node_title, data));
+    errors.extend(check_authorization(node_id, node_title, data));
+    errors.extend(check_timeout(node_id, node_title, data));
+    errors.extend(check_retry(node_id, node_title, data));
+
+    errors
+}
+
+fn check_method(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let message = match data.extra.get("method").and_then(Value::as_str) {
+        None => "HTTP request node missing 'method'".to_string(),
+        Some(m) if HTTP_METHODS.contains(&m.to_lowercase().as_str()) => return Vec::new(),
+        Some(m) => format!("Unknown HTTP method: {}", m),
+    };
+    vec![LintError::with_hint(
+        &rules::HTTP_INVALID_METHOD,
+        node_id,
+        node_title,
+        &message,
+        &format!("Use one of: {}", HTTP_METHODS.join(", ")),
+    )
+    .at("method")]
+}
+
+fn check_url(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let url = data
+        .extra
+        .get("url")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+
+    // A URL that starts with a reference gets its scheme and host at run time
+    let message = if url.is_empty() {
+        "HTTP request node has no 'url'".to_string()
+    } else if TEMPLATE_RE.find(url).is_some_and(|m| m.start() == 0) {
+        return Vec::new();
+    } else {
+        match Url::parse(&TEMPLATE_RE.replace_all(url, "x")) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => return Vec::new(),
+            Ok(parsed) => format!(
+                "URL scheme must be http or https, got '{}'",
+                parsed.scheme()
+            ),
+            Err(e) => format!("URL does not parse: {}", e),
+        }
+    };
+
+    vec![LintError::new(&rules::HTTP_INVALID_URL, node_id, node_title, &message).at("url")]
+}
+
+fn check_body(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let Some(body) = data.extra.get("body").filter(|b| !b.is_null()) else {
+        return errors;
+    };
+    let body_type = body.get("type").and_then(Value::as_str).unwrap_or("none");
+    let mut invalid = |message: String, path: String| {
+        errors.push(
+            LintError::new(&rules::HTTP_INVALID_BODY, node_id, node_title, &message).at(path),
+        );
+    };
+
+    if !BODY_TYPES.contains(&body_type) {
+        invalid(
+            format!("Unknown body type: {}", body_type),
+            "body/type".to_string(),
+        );
+        return errors;
+    }
+
+    // Older exports store the body as a string, newer ones as a list of fields
+    let items: Vec<&Value> = match body.get("data") {
+        Some(Value::Array(items)) => items.iter().collect(),
+        _ => Vec::new(),
+    };
+    let text = match body.get("data") {
+        Some(Value::String(s)) => s.clone(),
+        _ => items
+            .iter()
+            .filter_map(|item| item.get("value").and_then(Value::as_str))
+            .collect(),
+    };
+
+    match body_type {
+        "json" if !text.trim().is_empty() => {
+            // References may stand for any JSON value, so a number keeps the JSON valid
+            let substituted = TEMPLATE_RE.replace_all(&text, "0");
+            if let Err(e) = serde_json::from_str::<Value>(&substituted) {
+                invalid(
+                    format!("JSON body does not parse: {}", e),
+                    "body/data".to_string(),
+                );
+            }
+        }
+        "form-data" | "x-www-form-urlencoded" => {
+            for (i, item) in items.iter().enumerate() {
+                let key = item.get("key").and_then(Value::as_str).unwrap_or("");
+                let value = item.get("value").and_then(Value::as_str).unwrap_or("");
+                let is_file = item.get("type").and_then(Value::as_str) == Some("file");
+                let path = format!("body/data/{}", i);
+
+                // The editor keeps an empty row at the end of the list
+                if key.is_empty() && value.is_empty() && !is_file {
+                    continue;
+                }
+                if key.is_empty() {
+                    invalid(format!("Body field {} has no key", i), path.clone());
+                }
+                if is_file && body_type == "x-www-form-urlencoded" {
+                    invalid(format!("File field '{}' needs a form-data body", key), path);
+                } else if is_file && !has_file_selector(item) {
+                    invalid(format!("File field '{}' has no file selector", key), path);
+                }
+            }
+        }
+        "binary" if !items.iter().any(|item| has_file_selector(item)) => {
+            invalid(
+                "Binary body has no file selector".to_string(),
+                "body/data".to_string(),
+            );
+        }
+        _ => {}
+    }
+
+    errors
+}
+
+fn has_file_selector(item: &Value) -> bool {
+    item.get("file")
+        .and_then(Value::as_array)
+        .is_some_and(|s| !s.is_empty())
+}
+
+fn check_authorization(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let Some(auth) = data.extra.get("authorization") else {
+        return Vec::new();
+    };
+    if auth.get("type").and_then(Value::as_str) != Some("api-key") {
+        return Vec::new();
+    }
+
+    let config = auth.get("config").filter(|c| !c.is_null());
+    let field = |name: &str| {
+        config
+            .and_then(|c| c.get(name))
+            .and_then(Value::as_str)
+            .unwrap_or("")
+    };
+
+    let message = match (config, field("type")) {
+        (None, _) => "API key authorization has no 'config'".to_string(),
+        (_, "basic" | "bearer" | "custom") if field("api_key").trim().is_empty() => {
+            "API key authorization has no 'api_key'".to_string()
+        }
+        (_, "custom") if field("header").trim().is_empty() => {
+            "Custom API key authorization has no 'header'".to_string()
+        }
+        (_, "basic" | "bearer" | "custom") => return Vec::new(),
+        (_, "") => "API key authorization has no 'type'".to_string(),
+        (_, other) => format!("Unknown API key authorization type: {}", other),
+    };
+
+    vec![LintError::new(
+        &rules::HTTP_INCOMPLETE_AUTHORIZATION,
+        node_id,
+        node_title,
+        &message,
+    )
+    .at("authorization")]
+}
+
+fn check_timeout(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let Some(timeout) = data.extra.get("timeout").filter(|t| t.is_object()) else {
+        return errors;
+    };
+
+    for (kind, default_max) in DEFAULT_MAX_TIMEOUTS {
+        let Some(value) = timeout.get(kind).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        // Dify exports 0 when the node has no limit of its own
+        let max = timeout
+            .get(format!("max_{}_timeout", kind))
+            .and_then(Value::as_u64)
+            .filter(|m| *m > 0)
+            .unwrap_or(default_max);
+
+        let message = match value.as_f64() {
+            Some(v) if v <= 0.0 => format!("Timeout '{}' must be positive, got {}", kind, value),
+            Some(v) if v > max as f64 => {
+                format!(
+                    "Timeout '{}' of {} exceeds the maximum of {}",
+                    kind, value, max
+                )
+            }
+            Some(_) => continue,
+            None => format!("Timeout '{}' must be a number, got {}", kind, value),
+        };
+        errors.push(
+            LintError::new(&rules::HTTP_INVALID_TIMEOUT, node_id, node_title, &message)
+                .at(format!("timeout/{}", kind)),
+        );
+    }
+
+    errors
+}
+
+fn check_retry(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let Some(retry) = data.extra.get("retry_config") else {
+        return errors;
+    };
+    if retry.get("retry_enabled").and_then(Value::as_bool) != Some(true) {
+        return errors;
+    }
+
+    let settings = [
+        ("max_retries", MAX_RETRIES, ""),
+        ("retry_interval", RETRY_INTERVAL_MS, " ms"),
+    ];
+    for (field, range, unit) in settings {
+        let Some(value) = retry.get(field) else {
+            continue;
+        };
+        if value.as_u64().is_some_and(|v| range.contains(&v)) {
+            continue;
+        }
+        errors.push(
+            LintError::with_hint(
+                &rules::HTTP_INVALID_RETRY,
+                node_id,
+                node_title,
+                &format!("{} {} is out of range", field, value),
+                &format!(
+                    "Set {} between {}{} and {}{}",
+                    field,
+                    range.start(),
+                    unit,
+                    range.end(),
+                    unit
+                ),
+            )
+            .at(format!("retry_config/{}", field)),
+        );
+    }
+
+    errors
+}
@@ -1,11 +1,33 @@
-pub mod structure;
-pub mod nodes;
+pub mod chat_app;
+pub mod containers;
+pub mod dataflow;
 pub mod edges;
-pub mod variables;
+pub mod end;
+pub mod extractor;
+pub mod graph;
+pub mod http;
 pub mod models;
+pub mod nodes;
+pub mod references;
+pub mod secrets;
+pub mod start;
+pub mod structure;
+pub mod template;
+pub mod typing;
+pub mod variables;
 
-pub use structure::check_basic_structure;
-pub use nodes::check_nodes;
+pub use chat_app::check_chat_app;
+pub use containers::check_containers;
+pub use dataflow::{check_output_references, check_reference_order};
 pub use edges::check_edges;
-pub use variables::{check_conversation_variables, check_variable_references};
+pub use end::check_end_nodes;
+pub use graph::check_graph;
 pub use models::check_model_config;
+pub use nodes::check_nodes;
+pub use secrets::{check_raw_secrets, check_secrets};
+pub use structure::check_basic_structure;
+pub use typing::check_types;
+pub use variables::{
+    check_conversation_variables, check_environment_variables, check_scope_references,
+    check_variable_references,
+};
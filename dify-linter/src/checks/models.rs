@@ -1,4 +1,6 @@
-use crate::types::{LintError, Node};
+use crate::config::Config;
+use crate::rules;
+use crate::types::{LintContext, LintError, Model, Node};
 
 const VALID_MODELS: &[&str] = &[
     "ai21",
@@ -12,7 +14,7 @@ const VALID_MODELS: &[&str] = &[
 ];
 
 /// Check model configurations are valid
-pub fn check_model_config(nodes: &[Node]) -> Vec<LintError> {
+pub fn check_model_config(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
     let mut errors = Vec::new();
 
     for node in nodes {
@@ -25,26 +27,74 @@ pub fn check_model_config(nodes: &[Node]) -> Vec<LintError> {
         let node_type = node_data.node_type.as_deref().unwrap_or("");
         let node_title = node_data.title.as_deref().unwrap_or(node_type);
 
-        // Check LLM and question-classifier nodes
-        if node_type == "llm" || node_type == "question-classifier" {
+        // Check nodes that call a model
+        if matches!(
+            node_type,
+            "llm" | "question-classifier" | "parameter-extractor"
+        ) {
             if let Some(model) = &node_data.model {
-                if let Some(model_name) = &model.name {
-                    let model_lower = model_name.to_lowercase();
-                    let is_known = VALID_MODELS
-                        .iter()
-                        .any(|m| model_lower.contains(&m.to_lowercase()));
-
-                    if !is_known {
-                        errors.push(LintError::warning(
-                            node_id,
-                            node_title,
-                            &format!("Unknown model: {}", model_name),
-                        ));
-                    }
-                }
+                errors.extend(check_model(
+                    model,
+                    node_id,
+                    node_title,
+                    "model",
+                    &ctx.config,
+                ));
             }
         }
     }
 
     errors
 }
+
+/// Check a model's name and provider against the configured allow-lists;
+/// `path` points at the model mapping
+pub fn check_model(
+    model: &Model,
+    node_id: &str,
+    node_title: &str,
+    path: &str,
+    config: &Config,
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let allowed: Vec<&str> = match &config.models.allowed {
+        Some(models) => models.iter().map(String::as_str).collect(),
+        None => VALID_MODELS.to_vec(),
+    };
+
+    if let Some(model_name) = &model.name {
+        let model_lower = model_name.to_lowercase();
+        let is_known = allowed
+            .iter()
+            .any(|m| model_lower.contains(&m.to_lowercase()));
+
+        if !is_known {
+            errors.push(
+                LintError::new(
+                    &rules::UNKNOWN_MODEL,
+                    node_id,
+                    node_title,
+                    &format!("Unknown model: {}", model_name),
+                )
+                .at(format!("{}/name", path)),
+            );
+        }
+    }
+
+    // Providers are only checked when the config declares them
+    if let (Some(provider), Some(providers)) = (&model.provider, &config.models.providers) {
+        if !providers.contains(provider) {
+            errors.push(
+                LintError::new(
+                    &rules::UNKNOWN_MODEL_PROVIDER,
+                    node_id,
+                    node_title,
+                    &format!("Model provider not allowed: {}", provider),
+                )
+                .at(format!("{}/provider", path)),
+            );
+        }
+    }
+
+    errors
+}
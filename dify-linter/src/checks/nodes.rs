@@ -1,17 +1,89 @@
-use crate::types::{LintContext, LintError, Node, NodeData};
+use crate::checks::extractor::check_parameter_extractor_node;
+use crate::checks::http::check_http_request_node;
+use crate::checks::references::{RefKind, Target, VariableRef};
+use crate::checks::start::check_start_node;
+use crate::checks::template::check_template_node;
+use crate::fix::Fix;
+use crate::rules;
+use crate::schema::suggest;
+use crate::types::{Condition, LintContext, LintError, Node, NodeData};
 use serde_json::Value;
 use std::collections::HashSet;
 
+/// Built-in tool providers and their tools; an empty list allows any tool
+const KNOWN_TOOL_PROVIDERS: &[(&str, &[&str])] = &[
+    (
+        "langgenius/tavily/tavily",
+        &["tavily_search", "tavily_extract"],
+    ),
+    ("tavily", &["tavily_search", "tavily_extract"]),
+    ("json_process", &[]),
+];
+
+/// Code languages supported by the Dify sandbox
+const CODE_LANGUAGES: &[&str] = &["python3", "javascript"];
+
+/// Dify's LOOP_NODE_MAX_COUNT default
+const DEFAULT_LOOP_MAX_COUNT: u64 = 100;
+
+/// Types a loop variable can be declared with
+const LOOP_VARIABLE_TYPES: &[&str] = &[
+    "string",
+    "number",
+    "boolean",
+    "object",
+    "array[string]",
+    "array[number]",
+    "array[boolean]",
+    "array[object]",
+];
+
+/// Comparison operators supported in Dify conditions
+const COMPARISON_OPERATORS: &[&str] = &[
+    "contains",
+    "not contains",
+    "start with",
+    "end with",
+    "is",
+    "is not",
+    "empty",
+    "not empty",
+    "in",
+    "not in",
+    "all of",
+    "=",
+    "≠",
+    ">",
+    "<",
+    "≥",
+    "≤",
+    "null",
+    "not null",
+    "exists",
+    "not exists",
+];
+
+/// Classifier fields that read variables
+const CLASSIFIER_INPUTS: &[&str] = &["query_variable_selector", "instructions", "topics"];
+
+/// Similarity (0 to 1) above which two class names are reported as nearly identical
+const MIN_CLASS_NAME_SIMILARITY: f64 = 0.8;
+
 /// Check all nodes for issues
 pub fn check_nodes(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    for node in nodes {
+    for node in nodes.iter().filter(|n| !n.is_note()) {
         let node_id = node.id.as_deref().unwrap_or("");
         let node_data = match &node.data {
             Some(d) => d,
             None => {
-                errors.push(LintError::error(node_id, "unknown", "Node missing 'data'"));
+                errors.push(LintError::new(
+                    &rules::NODE_MISSING_DATA,
+                    node_id,
+                    "unknown",
+                    "Node missing 'data'",
+                ));
                 continue;
             }
         };
@@ -21,36 +93,55 @@ pub fn check_nodes(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
 
         // Check required fields
         if node_id.is_empty() {
-            errors.push(LintError::error("", node_title, "Node missing 'id'"));
+            errors.push(LintError::new(
+                &rules::NODE_MISSING_ID,
+                "",
+                node_title,
+                "Node missing 'id'",
+            ));
         }
 
         if node_type.is_empty() {
-            errors.push(LintError::error(node_id, node_title, "Node missing 'type'"));
+            errors.push(LintError::new(
+                &rules::NODE_MISSING_TYPE,
+                node_id,
+                node_title,
+                "Node missing 'type'",
+            ));
         }
 
         // Type-specific checks
         match node_type {
+            "start" => errors.extend(check_start_node(node_id, node_title, node_data)),
             "llm" => errors.extend(check_llm_node(node_id, node_title, node_data)),
             "if-else" => errors.extend(check_if_else_node(node_id, node_title, node_data, ctx)),
-            "question-classifier" => {
-                errors.extend(check_question_classifier_node(node_id, node_title, node_data, ctx))
-            }
+            "question-classifier" => errors.extend(check_question_classifier_node(
+                node, node_title, node_data, ctx,
+            )),
             "variable-aggregator" => {
-                errors.extend(check_variable_aggregator(node_id, node_title, node_data, ctx))
+                errors.extend(check_variable_aggregator(node_id, node_title, node_data))
             }
-            "answer" => errors.extend(check_answer_node(node_id, node_title, node_data, ctx)),
+            "answer" => errors.extend(check_answer_node(node_id, node_title, node_data)),
             "variable-assigner" => {
                 errors.extend(check_variable_assigner(node_id, node_title, node_data))
             }
-            "assigner" => {
-                errors.extend(check_assigner_v2(node_id, node_title, node_data))
-            }
+            "assigner" => errors.extend(check_assigner_v2(node_id, node_title, node_data)),
             "code" => errors.extend(check_code_node(node_id, node_title, node_data, ctx)),
-            "tool" => errors.extend(check_tool_node(node_id, node_title, node_data)),
-            "iteration" => errors.extend(check_iteration_node(node_id, node_title, node_data, ctx)),
-            "knowledge-retrieval" => {
-                errors.extend(check_knowledge_retrieval_node(node_id, node_title, node_data))
+            "tool" => errors.extend(check_tool_node(node_id, node_title, node_data, ctx)),
+            "iteration" => errors.extend(check_iteration_node(node_id, node_title, node_data)),
+            "loop" => errors.extend(check_loop_node(node_id, node_title, node_data, ctx)),
+            "http-request" => {
+                errors.extend(check_http_request_node(node_id, node_title, node_data))
+            }
+            "template-transform" => {
+                errors.extend(check_template_node(node_id, node_title, node_data))
             }
+            "parameter-extractor" => errors.extend(check_parameter_extractor_node(
+                node_id, node_title, node_data,
+            )),
+            "knowledge-retrieval" => errors.extend(check_knowledge_retrieval_node(
+                node_id, node_title, node_data, ctx,
+            )),
             _ => {}
         }
     }
@@ -65,7 +156,8 @@ fn check_llm_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintE
     let model = match &data.model {
         Some(m) => m,
         None => {
-            errors.push(LintError::error(
+            errors.push(LintError::new(
+                &rules::LLM_MISSING_MODEL,
                 node_id,
                 node_title,
                 "LLM node missing 'model' configuration",
@@ -75,36 +167,52 @@ fn check_llm_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintE
     };
 
     if model.name.is_none() {
-        errors.push(LintError::error(
-            node_id,
-            node_title,
-            "LLM model missing 'name'",
-        ));
+        errors.push(
+            LintError::new(
+                &rules::LLM_MODEL_MISSING_NAME,
+                node_id,
+                node_title,
+                "LLM model missing 'name'",
+            )
+            .at("model"),
+        );
     }
 
     if model.provider.is_none() {
-        errors.push(LintError::error(
-            node_id,
-            node_title,
-            "LLM model missing 'provider'",
-        ));
+        errors.push(
+            LintError::new(
+                &rules::LLM_MODEL_MISSING_PROVIDER,
+                node_id,
+                node_title,
+                "LLM model missing 'provider'",
+            )
+            .at("model"),
+        );
     }
 
     // Check prompt_template
     match &data.prompt_template {
         None => {
-            errors.push(LintError::warning(
-                node_id,
-                node_title,
-                "LLM node has empty prompt_template",
-            ));
+            errors.push(
+                LintError::new(
+                    &rules::LLM_EMPTY_PROMPT,
+                    node_id,
+                    node_title,
+                    "LLM node has empty prompt_template",
+                )
+                .at("prompt_template"),
+            );
         }
         Some(Value::Array(arr)) if arr.is_empty() => {
-            errors.push(LintError::warning(
-                node_id,
-                node_title,
-                "LLM node has empty prompt_template",
-            ));
+            errors.push(
+                LintError::new(
+                    &rules::LLM_EMPTY_PROMPT,
+                    node_id,
+                    node_title,
+                    "LLM node has empty prompt_template",
+                )
+                .at("prompt_template"),
+            );
         }
         _ => {}
     }
@@ -122,11 +230,16 @@ fn check_if_else_node(
     let mut errors = Vec::new();
 
     // Check for conditions in either legacy format (conditions) or new format (cases)
-    let has_conditions = data.conditions.as_ref().map(|c| !c.is_empty()).unwrap_or(false);
+    let has_conditions = data
+        .conditions
+        .as_ref()
+        .map(|c| !c.is_empty())
+        .unwrap_or(false);
     let has_cases = data.cases.as_ref().map(|c| !c.is_empty()).unwrap_or(false);
 
     if !has_conditions && !has_cases {
-        errors.push(LintError::error(
+        errors.push(LintError::new(
+            &rules::IF_ELSE_NO_CONDITIONS,
             node_id,
             node_title,
             "IF/ELSE node has no conditions",
@@ -135,18 +248,12 @@ fn check_if_else_node(
     }
 
     // Helper function to check conditions
-    let check_condition = |cond: &crate::types::Condition, errors: &mut Vec<LintError>| {
+    let check_condition = |cond: &Condition, path: &str, errors: &mut Vec<LintError>| {
         if let Some(var_selector) = &cond.variable_selector {
             if let Some(ref_node_id) = var_selector.first() {
-                // Check if referenced node exists (sys and conversation are special keywords)
-                if !ctx.node_exists(ref_node_id) && ref_node_id != "sys" && ref_node_id != "conversation" {
-                    errors.push(LintError::error_with_hint(
-                        node_id,
-                        node_title,
-                        &format!("IF/ELSE references non-existent node: {}", ref_node_id),
-                        "Use sys.query or valid node ID",
-                    ));
-                }
+                errors.extend(check_class_name_condition(
+                    cond, path, node_id, node_title, ctx,
+                ));
 
                 // Check if referencing start node with empty variables
                 if let Some(ref_node) = ctx.get_node(ref_node_id) {
@@ -163,15 +270,19 @@ fn check_if_else_node(
 
                             if !has_vars && var_selector.len() > 1 {
                                 let var_name = &var_selector[1];
-                                errors.push(LintError::error_with_hint(
-                                    node_id,
-                                    node_title,
-                                    &format!(
-                                        "References '{}' from start node, but start has no variables",
-                                        var_name
-                                    ),
-                                    "Either add variables to start node or use sys.query",
-                                ));
+                                errors.push(
+                                    LintError::with_hint(
+                                        &rules::START_VARIABLES_EMPTY,
+                                        node_id,
+                                        node_title,
+                                        &format!(
+                                            "References '{}' from start node, but start has no variables",
+                                            var_name
+                                        ),
+                                        "Either add variables to start node or use sys.query",
+                                    )
+                                    .at(format!("{}/variable_selector", path)),
+                                );
                             }
                         }
                     }
@@ -182,10 +293,11 @@ fn check_if_else_node(
 
     // Check legacy conditions format
     if let Some(conditions) = &data.conditions {
-        for cond_group in conditions {
+        for (i, cond_group) in conditions.iter().enumerate() {
             if let Some(conds) = &cond_group.conditions {
-                for cond in conds {
-                    check_condition(cond, &mut errors);
+                for (j, cond) in conds.iter().enumerate() {
+                    let path = format!("conditions/{}/conditions/{}", i, j);
+                    check_condition(cond, &path, &mut errors);
                 }
             }
         }
@@ -193,174 +305,341 @@ fn check_if_else_node(
 
     // Check new cases format
     if let Some(cases) = &data.cases {
-        for case in cases {
+        for (i, case) in cases.iter().enumerate() {
             if let Some(conds) = &case.conditions {
-                for cond in conds {
-                    check_condition(cond, &mut errors);
+                for (j, cond) in conds.iter().enumerate() {
+                    let path = format!("cases/{}/conditions/{}", i, j);
+                    check_condition(cond, &path, &mut errors);
                 }
             }
         }
     }
 
+    // Check that every case and the else branch has an edge
+    let edge_handles: HashSet<&str> = ctx
+        .edges
+        .iter()
+        .filter(|e| e.source.as_deref() == Some(node_id))
+        .filter_map(|e| e.source_handle.as_deref())
+        .collect();
+
+    let cases: Vec<(&str, String)> = match &data.cases {
+        Some(cases) if has_cases => cases
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some((c.case_id.as_deref()?, format!("cases/{}", i))))
+            .collect(),
+        _ => vec![("true", "conditions".to_string())],
+    };
+
+    for (case_id, path) in cases {
+        if !edge_handles.contains(case_id) {
+            errors.push(
+                LintError::new(
+                    &rules::IF_ELSE_BRANCH_WITHOUT_EDGE,
+                    node_id,
+                    node_title,
+                    &format!("Case '{}' has no outgoing edge - will go nowhere", case_id),
+                )
+                .at(path),
+            );
+        }
+    }
+
+    if !edge_handles.contains("false") {
+        errors.push(LintError::new(
+            &rules::IF_ELSE_BRANCH_WITHOUT_EDGE,
+            node_id,
+            node_title,
+            "ELSE branch has no outgoing edge - will go nowhere",
+        ));
+    }
+
     errors
 }
 
 /// Check Question Classifier configuration
 fn check_question_classifier_node(
-    node_id: &str,
+    node: &Node,
     node_title: &str,
     data: &NodeData,
     ctx: &LintContext,
 ) -> Vec<LintError> {
     let mut errors = Vec::new();
+    let node_id = node.id.as_deref().unwrap_or("");
 
     let classes = match &data.classes {
         Some(c) if !c.is_empty() => c,
         _ => {
-            errors.push(LintError::error(
-                node_id,
-                node_title,
-                "Question Classifier has no classes",
-            ));
+            errors.push(
+                LintError::new(
+                    &rules::CLASSIFIER_NO_CLASSES,
+                    node_id,
+                    node_title,
+                    "Question Classifier has no classes",
+                )
+                .at("classes"),
+            );
             return errors;
         }
     };
 
     // Check model
     if data.model.is_none() {
-        errors.push(LintError::error(
+        errors.push(LintError::new(
+            &rules::CLASSIFIER_MISSING_MODEL,
             node_id,
             node_title,
             "Question Classifier missing 'model'",
         ));
     }
 
-    // Check each class has an id and name
-    let mut class_ids = HashSet::new();
-    for cls in classes {
-        match &cls.id {
-            Some(id) => {
-                class_ids.insert(id.clone());
-            }
+    // Check each class has a unique id and name
+    let mut class_ids: Vec<&str> = Vec::new();
+    let mut class_names: Vec<&str> = Vec::new();
+    for (i, cls) in classes.iter().enumerate() {
+        let path = format!("classes/{}", i);
+        match cls.id.as_deref() {
+            Some(id) if class_ids.contains(&id) => errors.push(
+                LintError::new(
+                    &rules::CLASSIFIER_DUPLICATE_CLASS,
+                    node_id,
+                    node_title,
+                    &format!("Duplicate class id: {}", id),
+                )
+                .at(&path),
+            ),
+            Some(id) => class_ids.push(id),
             None => {
-                errors.push(LintError::error(node_id, node_title, "Class missing 'id'"));
+                errors.push(
+                    LintError::new(
+                        &rules::CLASS_MISSING_ID,
+                        node_id,
+                        node_title,
+                        "Class missing 'id'",
+                    )
+                    .at(&path),
+                );
             }
         }
 
-        if cls.name.is_none() {
-            let cls_id = cls.id.as_deref().unwrap_or("unknown");
-            errors.push(LintError::error(
-                node_id,
-                node_title,
-                &format!("Class {} missing 'name'", cls_id),
-            ));
+        let cls_id = cls.id.as_deref().unwrap_or("unknown");
+        let name = cls.name.as_deref().unwrap_or("").trim();
+        if name.is_empty() {
+            errors.push(
+                LintError::new(
+                    &rules::CLASS_MISSING_NAME,
+                    node_id,
+                    node_title,
+                    &format!("Class {} missing 'name'", cls_id),
+                )
+                .at(&path),
+            );
+            continue;
+        }
+
+        let normalized = |n: &str| n.trim().to_lowercase();
+        if let Some(other) = class_names
+            .iter()
+            .find(|n| normalized(n) == normalized(name))
+        {
+            errors.push(
+                LintError::new(
+                    &rules::CLASSIFIER_DUPLICATE_CLASS,
+                    node_id,
+                    node_title,
+                    &format!("Duplicate class name: '{}' and '{}'", other, name),
+                )
+                .at(format!("{}/name", path)),
+            );
+        } else if let Some(other) = class_names.iter().find(|n| {
+            strsim::normalized_damerau_levenshtein(&normalized(n), &normalized(name))
+                >= MIN_CLASS_NAME_SIMILARITY
+                && !is_class_series(n, name)
+        }) {
+            errors.push(
+                LintError::with_hint(
+                    &rules::CLASSIFIER_SIMILAR_CLASSES,
+                    node_id,
+                    node_title,
+                    &format!(
+                        "Class names '{}' and '{}' are nearly identical",
+                        other, name
+                    ),
+                    "Merge the classes or make their names clearly different",
+                )
+                .at(format!("{}/name", path)),
+            );
         }
+        class_names.push(name);
     }
 
     // Check that edges exist for each class
-    let edge_handles: HashSet<String> = ctx
+    let edge_handles: HashSet<&str> = ctx
         .edges
         .iter()
         .filter(|e| e.source.as_deref() == Some(node_id))
-        .filter_map(|e| e.source_handle.clone())
+        .filter_map(|e| e.source_handle.as_deref())
         .collect();
 
     for cls_id in &class_ids {
         if !edge_handles.contains(cls_id) {
-            errors.push(LintError::warning(
-                node_id,
-                node_title,
-                &format!("Class '{}' has no outgoing edge - will go nowhere", cls_id),
-            ));
-        }
-    }
-
-    // Check query_variable_selector
-    if let Some(query_var) = &data.query_variable_selector {
-        if query_var.len() >= 2 {
-            let source = &query_var[0];
-            let var = &query_var[1];
-            if source == "sys" && var == "query" {
-                // Valid
-            } else if !ctx.node_exists(source) {
-                errors.push(LintError::error_with_hint(
+            errors.push(
+                LintError::new(
+                    &rules::CLASS_WITHOUT_EDGE,
                     node_id,
                     node_title,
-                    &format!(
-                        "query_variable_selector references non-existent node: {}",
-                        source
-                    ),
-                    "Use sys.query or valid node ID",
-                ));
-            } else {
-                // Check if referencing start node with empty variables
-                if let Some(ref_node) = ctx.get_node(source) {
-                    if let Some(ref_data) = &ref_node.data {
-                        if ref_data.node_type.as_deref() == Some("start") {
-                            let has_vars = ref_data
-                                .variables
-                                .as_ref()
-                                .map(|v| match v {
-                                    Value::Array(arr) => !arr.is_empty(),
-                                    _ => false,
-                                })
-                                .unwrap_or(false);
+                    &format!("Class '{}' has no outgoing edge - will go nowhere", cls_id),
+                )
+                .at("classes"),
+            );
+        }
+    }
 
-                            if !has_vars {
-                                errors.push(LintError::error_with_hint(
-                                    node_id,
-                                    node_title,
-                                    &format!(
-                                        "query_variable_selector references '{}' from start node, but start has no variables",
-                                        var
-                                    ),
-                                    "Either add variables to start node or use sys.query",
-                                ));
-                            }
-                        }
-                    }
-                }
+    // Check the query input and references in the instructions
+    let inputs = ctx
+        .references(node)
+        .iter()
+        .filter(|r| CLASSIFIER_INPUTS.iter().any(|f| r.path.starts_with(f)));
+    for r in inputs {
+        match r.resolve(ctx) {
+            // Missing nodes and other unknown outputs are reported by the reference checks
+            Target::UnknownOutput(ref_node, outputs)
+                if ref_node.node_type() == "start" && outputs.is_empty() =>
+            {
+                errors.push(
+                    LintError::with_hint(
+                        &rules::START_VARIABLES_EMPTY,
+                        node_id,
+                        node_title,
+                        &format!(
+                            "References '{}' from start node, but start has no variables",
+                            r.variable
+                        ),
+                        "Either add variables to start node or use sys.query",
+                    )
+                    .at(&r.path),
+                );
             }
+            _ => {}
         }
     }
 
     errors
 }
 
-/// Check Variable Aggregator configuration
-fn check_variable_aggregator(
+/// Classes named as a series, e.g. "Plan A" / "Plan B" or "Tier1" / "Tier2", which only
+/// differ in a trailing label or number
+fn is_class_series(a: &str, b: &str) -> bool {
+    fn split(name: &str) -> (String, String) {
+        let name = name.trim().to_lowercase();
+        match name.rsplit_once(char::is_whitespace) {
+            Some((stem, label)) => (stem.trim_end().to_string(), label.to_string()),
+            None => {
+                let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+                (stem.to_string(), name[stem.len()..].to_string())
+            }
+        }
+    }
+    let is_label = |label: &str| {
+        !label.is_empty()
+            && (label.chars().count() <= 2 || label.chars().all(|c| c.is_ascii_digit()))
+    };
+
+    let ((stem_a, label_a), (stem_b, label_b)) = (split(a), split(b));
+    !stem_a.is_empty()
+        && stem_a == stem_b
+        && label_a != label_b
+        && is_label(&label_a)
+        && is_label(&label_b)
+}
+
+/// A condition on a classifier's `class_name` must compare it with one of its classes
+fn check_class_name_condition(
+    cond: &Condition,
+    path: &str,
     node_id: &str,
     node_title: &str,
-    data: &NodeData,
     ctx: &LintContext,
-) -> Vec<LintError> {
+) -> Option<LintError> {
+    let [source, variable] = cond.variable_selector.as_deref()? else {
+        return None;
+    };
+    if variable != "class_name" {
+        return None;
+    }
+    let reference = VariableRef {
+        node: source.clone(),
+        variable: variable.clone(),
+        kind: RefKind::Selector,
+        path: format!("{}/value", path),
+    };
+    let Target::Output(classifier) = reference.resolve(ctx) else {
+        return None;
+    };
+    if classifier.node_type() != "question-classifier" {
+        return None;
+    }
+
+    let value = cond.value.as_ref()?.as_str()?;
+    let names: Vec<&str> = classifier
+        .data
+        .as_ref()?
+        .classes
+        .iter()
+        .flatten()
+        .filter_map(|c| c.name.as_deref())
+        .collect();
+    let matches = |name: &&str| match cond.comparison_operator.as_deref() {
+        Some("is" | "is not") => *name == value,
+        Some("contains" | "not contains") => name.contains(value),
+        Some("start with") => name.starts_with(value),
+        Some("end with") => name.ends_with(value),
+        _ => true,
+    };
+    if value.is_empty() || names.is_empty() || names.iter().any(matches) {
+        return None;
+    }
+
+    let hint = match suggest(value, names.iter().copied()) {
+        Some(name) => format!("Did you mean '{}'?", name),
+        None => format!("Classes: {}", names.join(", ")),
+    };
+    let title = classifier
+        .data
+        .as_ref()
+        .and_then(|d| d.title.as_deref())
+        .unwrap_or(source);
+    Some(
+        LintError::with_hint(
+            &rules::CLASS_NAME_UNKNOWN,
+            node_id,
+            node_title,
+            &format!(
+                "Condition compares {}.class_name with '{}', which is not one of its classes",
+                title, value
+            ),
+            &hint,
+        )
+        .at(reference.path),
+    )
+}
+
+/// Check Variable Aggregator configuration
+fn check_variable_aggregator(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    let variables = match &data.variables {
-        Some(Value::Array(arr)) if !arr.is_empty() => arr,
-        _ => {
-            errors.push(LintError::warning(
+    // The referenced variables are checked with every other reference
+    if !matches!(&data.variables, Some(Value::Array(arr)) if !arr.is_empty()) {
+        errors.push(
+            LintError::new(
+                &rules::AGGREGATOR_NO_VARIABLES,
                 node_id,
                 node_title,
                 "Variable Aggregator has no variables",
-            ));
-            return errors;
-        }
-    };
-
-    // Check each referenced variable exists
-    for var in variables {
-        if let Value::Array(var_arr) = var {
-            if let Some(Value::String(ref_node_id)) = var_arr.first() {
-                if !ctx.node_exists(ref_node_id) {
-                    errors.push(LintError::error(
-                        node_id,
-                        node_title,
-                        &format!("References non-existent node: {}", ref_node_id),
-                    ));
-                }
-            }
-        }
+            )
+            .at("variables"),
+        );
     }
 
     errors
@@ -372,7 +651,8 @@ fn check_variable_assigner(node_id: &str, node_title: &str, data: &NodeData) ->
 
     // WARNING: Variable Assigner may cause client-side errors in Dify 1.10.x
     // See: https://github.com/langgenius/dify/issues/XXXX
-    errors.push(LintError::warning_with_hint(
+    errors.push(LintError::with_hint(
+        &rules::VARIABLE_ASSIGNER_UNSTABLE,
         node_id,
         node_title,
         "Variable Assigner node may cause 'e.slice is not a function' error in Dify 1.10.x",
@@ -381,15 +661,24 @@ fn check_variable_assigner(node_id: &str, node_title: &str, data: &NodeData) ->
 
     // Check variables have write_mode
     if let Some(Value::Array(vars)) = &data.variables {
-        for var in vars {
+        for (i, var) in vars.iter().enumerate() {
             if let Value::Object(obj) = var {
                 if !obj.contains_key("write_mode") {
-                    errors.push(LintError::error_with_hint(
-                        node_id,
-                        node_title,
-                        "Variable Assigner variable missing 'write_mode'",
-                        "Add: write_mode: 'over-write'",
-                    ));
+                    errors.push(
+                        LintError::with_hint(
+                            &rules::VARIABLE_ASSIGNER_MISSING_WRITE_MODE,
+                            node_id,
+                            node_title,
+                            "Variable Assigner variable missing 'write_mode'",
+                            "Add: write_mode: 'over-write'",
+                        )
+                        .at(format!("variables/{}", i))
+                        .with_fix(Fix::set(
+                            format!("variables/{}", i),
+                            "write_mode",
+                            "over-write",
+                        )),
+                    );
                 }
             }
         }
@@ -405,44 +694,61 @@ fn check_assigner_v2(node_id: &str, node_title: &str, data: &NodeData) -> Vec<Li
     // Check version field
     let version = data.extra.get("version").and_then(|v| v.as_str());
     if version != Some("2") {
-        errors.push(LintError::warning_with_hint(
-            node_id,
-            node_title,
-            "Assigner node should have version: \"2\"",
-            "Add: version: \"2\"",
-        ));
+        errors.push(
+            LintError::with_hint(
+                &rules::ASSIGNER_VERSION,
+                node_id,
+                node_title,
+                "Assigner node should have version: \"2\"",
+                "Add: version: \"2\"",
+            )
+            .at("version")
+            .with_fix(Fix::set("", "version", "'2'")),
+        );
     }
 
     // Check items array exists
     let items = data.extra.get("items").and_then(|v| v.as_array());
     match items {
         None => {
-            errors.push(LintError::error_with_hint(
-                node_id,
-                node_title,
-                "Assigner V2 node missing 'items' array",
-                "Add items array with variable_selector, input_type, operation, value",
-            ));
+            errors.push(
+                LintError::with_hint(
+                    &rules::ASSIGNER_MISSING_ITEMS,
+                    node_id,
+                    node_title,
+                    "Assigner V2 node missing 'items' array",
+                    "Add items array with variable_selector, input_type, operation, value",
+                )
+                .at("items"),
+            );
         }
         Some(items_arr) => {
             for (i, item) in items_arr.iter().enumerate() {
                 if let Some(obj) = item.as_object() {
                     // Check required fields
                     if !obj.contains_key("variable_selector") {
-                        errors.push(LintError::error_with_hint(
-                            node_id,
-                            node_title,
-                            &format!("items[{}] missing 'variable_selector'", i),
-                            "Add: variable_selector: [conversation, var_name]",
-                        ));
+                        errors.push(
+                            LintError::with_hint(
+                                &rules::ASSIGNER_ITEM_INCOMPLETE,
+                                node_id,
+                                node_title,
+                                &format!("items[{}] missing 'variable_selector'", i),
+                                "Add: variable_selector: [conversation, var_name]",
+                            )
+                            .at(format!("items/{}", i)),
+                        );
                     }
                     if !obj.contains_key("operation") {
-                        errors.push(LintError::error_with_hint(
-                            node_id,
-                            node_title,
-                            &format!("items[{}] missing 'operation'", i),
-                            "Add: operation: overwrite (or append, clear, etc.)",
-                        ));
+                        errors.push(
+                            LintError::with_hint(
+                                &rules::ASSIGNER_ITEM_INCOMPLETE,
+                                node_id,
+                                node_title,
+                                &format!("items[{}] missing 'operation'", i),
+                                "Add: operation: overwrite (or append, clear, etc.)",
+                            )
+                            .at(format!("items/{}", i)),
+                        );
                     }
                 }
             }
@@ -453,44 +759,19 @@ fn check_assigner_v2(node_id: &str, node_title: &str, data: &NodeData) -> Vec<Li
 }
 
 /// Check Answer node configuration
-fn check_answer_node(
-    node_id: &str,
-    node_title: &str,
-    data: &NodeData,
-    ctx: &LintContext,
-) -> Vec<LintError> {
+fn check_answer_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    let answer = match &data.answer {
-        Some(a) if !a.is_empty() => a,
-        _ => {
-            errors.push(LintError::warning(
+    if data.answer.as_deref().unwrap_or("").is_empty() {
+        errors.push(
+            LintError::new(
+                &rules::ANSWER_EMPTY,
                 node_id,
                 node_title,
                 "Answer node has empty answer",
-            ));
-            return errors;
-        }
-    };
-
-    // Check variable references in answer using regex
-    let re = regex::Regex::new(r"\{\{#([^#]+)#\}\}").unwrap();
-    for cap in re.captures_iter(answer) {
-        if let Some(ref_str) = cap.get(1) {
-            let parts: Vec<&str> = ref_str.as_str().split('.').collect();
-            if let Some(&ref_node_id) = parts.first() {
-                if !ctx.node_exists(ref_node_id)
-                    && ref_node_id != "sys"
-                    && ref_node_id != "conversation"
-                {
-                    errors.push(LintError::error(
-                        node_id,
-                        node_title,
-                        &format!("Answer references non-existent node: {}", ref_node_id),
-                    ));
-                }
-            }
-        }
+            )
+            .at("answer"),
+        );
     }
 
     errors
@@ -508,30 +789,50 @@ fn check_code_node(
     // Check code field exists
     let code = data.extra.get("code").and_then(|v| v.as_str());
     if code.is_none() || code.map(|c| c.is_empty()).unwrap_or(true) {
-        errors.push(LintError::error(
-            node_id,
-            node_title,
-            "Code node missing 'code' field",
-        ));
+        errors.push(
+            LintError::new(
+                &rules::CODE_MISSING_CODE,
+                node_id,
+                node_title,
+                "Code node missing 'code' field",
+            )
+            .at("code"),
+        );
     }
 
     // Check code_language
+    let languages: Vec<&str> = match &ctx.config.code.languages {
+        Some(langs) => langs.iter().map(String::as_str).collect(),
+        None => CODE_LANGUAGES.to_vec(),
+    };
     let lang = data.extra.get("code_language").and_then(|v| v.as_str());
     match lang {
         None => {
-            errors.push(LintError::warning_with_hint(
-                node_id,
-                node_title,
-                "Code node missing 'code_language'",
-                "Add: code_language: python3",
-            ));
+            errors.push(
+                LintError::with_hint(
+                    &rules::CODE_MISSING_LANGUAGE,
+                    node_id,
+                    node_title,
+                    "Code node missing 'code_language'",
+                    "Add: code_language: python3",
+                )
+                .at("code_language"),
+            );
         }
-        Some(l) if l != "python3" && l != "javascript" => {
-            errors.push(LintError::warning(
-                node_id,
-                node_title,
-                &format!("Unknown code_language: {} (expected python3 or javascript)", l),
-            ));
+        Some(l) if !languages.contains(&l) => {
+            errors.push(
+                LintError::new(
+                    &rules::CODE_UNKNOWN_LANGUAGE,
+                    node_id,
+                    node_title,
+                    &format!(
+                        "Unknown code_language: {} (expected {})",
+                        l,
+                        languages.join(" or ")
+                    ),
+                )
+                .at("code_language"),
+            );
         }
         _ => {}
     }
@@ -539,88 +840,102 @@ fn check_code_node(
     // Check outputs
     let outputs = data.extra.get("outputs");
     if outputs.is_none() {
-        errors.push(LintError::warning_with_hint(
-            node_id,
-            node_title,
-            "Code node missing 'outputs' definition",
-            "Add outputs with variable names and types",
-        ));
-    }
-
-    // Check variable references in code
-    if let Some(code_str) = code {
-        let re = regex::Regex::new(r"\{\{#([^#]+)#\}\}").unwrap();
-        for cap in re.captures_iter(code_str) {
-            if let Some(ref_str) = cap.get(1) {
-                let parts: Vec<&str> = ref_str.as_str().split('.').collect();
-                if let Some(&ref_node_id) = parts.first() {
-                    if !ctx.node_exists(ref_node_id)
-                        && ref_node_id != "sys"
-                        && ref_node_id != "conversation"
-                    {
-                        errors.push(LintError::error(
-                            node_id,
-                            node_title,
-                            &format!("Code references non-existent node: {}", ref_node_id),
-                        ));
-                    }
-                }
-            }
-        }
+        errors.push(
+            LintError::with_hint(
+                &rules::CODE_MISSING_OUTPUTS,
+                node_id,
+                node_title,
+                "Code node missing 'outputs' definition",
+                "Add outputs with variable names and types",
+            )
+            .at("outputs"),
+        );
     }
 
     errors
 }
 
 /// Check Tool node configuration
-fn check_tool_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+fn check_tool_node(
+    node_id: &str,
+    node_title: &str,
+    data: &NodeData,
+    ctx: &LintContext,
+) -> Vec<LintError> {
     let mut errors = Vec::new();
 
     // Check provider_id
     let provider_id = data.extra.get("provider_id").and_then(|v| v.as_str());
     if provider_id.is_none() || provider_id.map(|p| p.is_empty()).unwrap_or(true) {
-        errors.push(LintError::error_with_hint(
-            node_id,
-            node_title,
-            "Tool node missing 'provider_id'",
-            "Add: provider_id: langgenius/tavily/tavily",
-        ));
+        errors.push(
+            LintError::with_hint(
+                &rules::TOOL_MISSING_PROVIDER,
+                node_id,
+                node_title,
+                "Tool node missing 'provider_id'",
+                "Add: provider_id: langgenius/tavily/tavily",
+            )
+            .at("provider_id"),
+        );
     }
 
     // Check tool_name
     let tool_name = data.extra.get("tool_name").and_then(|v| v.as_str());
     if tool_name.is_none() || tool_name.map(|t| t.is_empty()).unwrap_or(true) {
-        errors.push(LintError::error_with_hint(
-            node_id,
-            node_title,
-            "Tool node missing 'tool_name'",
-            "Add: tool_name: tavily_search or tavily_extract",
-        ));
+        errors.push(
+            LintError::with_hint(
+                &rules::TOOL_MISSING_NAME,
+                node_id,
+                node_title,
+                "Tool node missing 'tool_name'",
+                "Add: tool_name: tavily_search or tavily_extract",
+            )
+            .at("tool_name"),
+        );
     }
 
     // Validate known providers and tools
     if let (Some(provider), Some(tool)) = (provider_id, tool_name) {
-        match provider {
-            "langgenius/tavily/tavily" | "tavily" => {
-                if tool != "tavily_search" && tool != "tavily_extract" {
-                    errors.push(LintError::warning(
+        let known_tools: Option<Vec<&str>> = match &ctx.config.tools.providers {
+            Some(providers) => providers
+                .get(provider)
+                .map(|tools| tools.iter().map(String::as_str).collect()),
+            None => KNOWN_TOOL_PROVIDERS
+                .iter()
+                .find(|(p, _)| *p == provider)
+                .map(|(_, tools)| tools.to_vec()),
+        };
+
+        match known_tools {
+            None => {
+                // Unknown provider - just a warning
+                errors.push(
+                    LintError::new(
+                        &rules::TOOL_UNKNOWN_PROVIDER,
                         node_id,
                         node_title,
-                        &format!("Unknown Tavily tool: {} (expected tavily_search or tavily_extract)", tool),
-                    ));
-                }
-            }
-            "json_process" => {
-                // JSON process tool - valid
+                        &format!("Unknown tool provider: {}", provider),
+                    )
+                    .at("provider_id"),
+                );
             }
-            _ => {
-                // Unknown provider - just a warning
-                errors.push(LintError::warning(
-                    node_id,
-                    node_title,
-                    &format!("Unknown tool provider: {}", provider),
-                ));
+            Some(tools) if !tools.is_empty() && !tools.contains(&tool) => {
+                errors.push(
+                    LintError::new(
+                        &rules::TOOL_UNKNOWN_TOOL,
+                        node_id,
+                        node_title,
+                        &format!(
+                            "Unknown {} tool: {} (expected {})",
+                            provider,
+                            tool,
+                            tools.join(" or ")
+                        ),
+                    )
+                    .at("tool_name"),
+                );
             }
+            _ => {}
         }
     }
 
@@ -628,7 +943,47 @@ fn check_tool_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<Lint
 }
 
 /// Check Iteration node configuration
-fn check_iteration_node(
+fn check_iteration_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    // Check iterator_selector
+    let iterator = data
+        .extra
+        .get("iterator_selector")
+        .and_then(|v| v.as_array());
+    if iterator.is_none() {
+        errors.push(
+            LintError::with_hint(
+                &rules::ITERATION_MISSING_ITERATOR,
+                node_id,
+                node_title,
+                "Iteration node missing 'iterator_selector'",
+                "Add: iterator_selector: [node_id, output_var]",
+            )
+            .at("iterator_selector"),
+        );
+    }
+
+    // Check output_selector
+    let output = data.extra.get("output_selector").and_then(|v| v.as_array());
+    if output.is_none() {
+        errors.push(
+            LintError::with_hint(
+                &rules::ITERATION_MISSING_OUTPUT,
+                node_id,
+                node_title,
+                "Iteration node missing 'output_selector'",
+                "Add: output_selector: [inner_node_id, output_var]",
+            )
+            .at("output_selector"),
+        );
+    }
+
+    errors
+}
+
+/// Check Loop node configuration
+fn check_loop_node(
     node_id: &str,
     node_title: &str,
     data: &NodeData,
@@ -636,80 +991,222 @@ fn check_iteration_node(
 ) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    // Check iterator_selector
-    let iterator = data.extra.get("iterator_selector").and_then(|v| v.as_array());
-    match iterator {
-        None => {
-            errors.push(LintError::error_with_hint(
+    // Check loop_count
+    let max_count = ctx.config.loops.max_count.unwrap_or(DEFAULT_LOOP_MAX_COUNT);
+    let count = data.extra.get("loop_count");
+    let parsed = count.and_then(|c| match c {
+        Value::String(s) => s.trim().parse::<u64>().ok(),
+        _ => c.as_u64(),
+    });
+    let message = match (count, parsed) {
+        (None, _) => Some("Loop node missing 'loop_count'".to_string()),
+        (Some(c), None) => Some(format!("loop_count must be a positive integer, got {}", c)),
+        (_, Some(0)) => Some("loop_count must be at least 1".to_string()),
+        (_, Some(n)) if n > max_count => Some(format!(
+            "loop_count {} exceeds the maximum of {}",
+            n, max_count
+        )),
+        _ => None,
+    };
+    if let Some(message) = message {
+        errors.push(
+            LintError::with_hint(
+                &rules::LOOP_INVALID_COUNT,
                 node_id,
                 node_title,
-                "Iteration node missing 'iterator_selector'",
-                "Add: iterator_selector: [node_id, output_var]",
-            ));
+                &message,
+                &format!("Set loop_count between 1 and {}", max_count),
+            )
+            .at("loop_count"),
+        );
+    }
+
+    // Check loop_variables
+    let variables = data.extra.get("loop_variables").and_then(|v| v.as_array());
+    let mut labels = HashSet::new();
+    for (i, var) in variables.into_iter().flatten().enumerate() {
+        let path = format!("loop_variables/{}", i);
+        let label = var.get("label").and_then(|v| v.as_str()).unwrap_or("");
+        let mut invalid = |message: String, field: &str| {
+            errors.push(
+                LintError::new(&rules::LOOP_INVALID_VARIABLE, node_id, node_title, &message)
+                    .at(format!("{}{}", path, field)),
+            );
+        };
+
+        if label.is_empty() {
+            invalid(format!("Loop variable {} has no 'label'", i), "");
+        } else if !labels.insert(label) {
+            invalid(format!("Duplicate loop variable: {}", label), "/label");
         }
-        Some(arr) => {
-            if let Some(source) = arr.first().and_then(|v| v.as_str()) {
-                if !ctx.node_exists(source) && source != "sys" && source != "conversation" {
-                    errors.push(LintError::error(
-                        node_id,
-                        node_title,
-                        &format!("Iteration references non-existent node: {}", source),
-                    ));
+
+        let var_type = var.get("var_type").and_then(|v| v.as_str());
+        match var_type {
+            Some(t) if LOOP_VARIABLE_TYPES.contains(&t) => {}
+            Some(t) => invalid(format!("Unknown loop variable type: {}", t), "/var_type"),
+            None => invalid(format!("Loop variable '{}' has no 'var_type'", label), ""),
+        }
+
+        let value = var.get("value");
+        match var.get("value_type").and_then(|v| v.as_str()) {
+            Some("constant") => {
+                if let (Some(t), Some(value)) = (var_type, value) {
+                    if !constant_matches(t, value) {
+                        invalid(
+                            format!("Initial value of '{}' is not a valid {}", label, t),
+                            "/value",
+                        );
+                    }
+                }
+            }
+            Some("variable") => {
+                let selector = value.and_then(|v| v.as_array()).filter(|s| !s.is_empty());
+                if selector.is_none() {
+                    invalid(
+                        format!("Loop variable '{}' has no variable selector", label),
+                        "/value",
+                    );
                 }
             }
+            Some(other) => invalid(
+                format!("Unknown loop variable value_type: {}", other),
+                "/value_type",
+            ),
+            None => invalid(format!("Loop variable '{}' has no 'value_type'", label), ""),
         }
     }
 
-    // Check output_selector
-    let output = data.extra.get("output_selector").and_then(|v| v.as_array());
-    if output.is_none() {
-        errors.push(LintError::warning_with_hint(
-            node_id,
-            node_title,
-            "Iteration node missing 'output_selector'",
-            "Add: output_selector: [inner_node_id, output_var]",
-        ));
+    // Check break_conditions
+    let conditions = data
+        .extra
+        .get("break_conditions")
+        .and_then(|v| v.as_array());
+    for (i, cond) in conditions.into_iter().flatten().enumerate() {
+        let path = format!("break_conditions/{}", i);
+        let selector: Vec<&str> = cond
+            .get("variable_selector")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.as_str())
+            .collect();
+        let operator = cond.get("comparison_operator").and_then(|v| v.as_str());
+
+        let message = match (selector.as_slice(), operator) {
+            ([], _) => Some(format!("Break condition {} has no variable_selector", i)),
+            (_, None) => Some(format!("Break condition {} has no comparison_operator", i)),
+            (_, Some(op)) if !COMPARISON_OPERATORS.contains(&op) => {
+                Some(format!("Unknown comparison_operator: {}", op))
+            }
+            _ => None,
+        };
+        if let Some(message) = message {
+            errors.push(
+                LintError::new(
+                    &rules::LOOP_INVALID_BREAK_CONDITION,
+                    node_id,
+                    node_title,
+                    &message,
+                )
+                .at(path),
+            );
+        }
     }
 
     errors
 }
 
+/// Whether a constant loop variable value is valid for its type
+fn constant_matches(var_type: &str, value: &Value) -> bool {
+    // The editor stores constants as strings, so parse them where needed
+    let parsed = match value {
+        Value::String(s) if var_type != "string" => match serde_json::from_str(s) {
+            Ok(v) => v,
+            Err(_) => return s.is_empty(),
+        },
+        v => v.clone(),
+    };
+    match var_type {
+        "string" => parsed.is_string(),
+        "number" => parsed.is_number(),
+        "boolean" => parsed.is_boolean(),
+        "object" => parsed.is_object(),
+        _ => parsed.is_array(),
+    }
+}
+
 /// Check Knowledge Retrieval node configuration
 fn check_knowledge_retrieval_node(
     node_id: &str,
     node_title: &str,
     data: &NodeData,
+    ctx: &LintContext,
 ) -> Vec<LintError> {
     let mut errors = Vec::new();
 
     // Check dataset_ids
     let dataset_ids = data.extra.get("dataset_ids").and_then(|v| v.as_array());
     if dataset_ids.is_none() || dataset_ids.map(|d| d.is_empty()).unwrap_or(true) {
-        errors.push(LintError::error_with_hint(
-            node_id,
-            node_title,
-            "Knowledge Retrieval node missing 'dataset_ids'",
-            "Add dataset_ids array with knowledge base UUIDs",
-        ));
+        errors.push(
+            LintError::with_hint(
+                &rules::KNOWLEDGE_MISSING_DATASETS,
+                node_id,
+                node_title,
+                "Knowledge Retrieval node missing 'dataset_ids'",
+                "Add dataset_ids array with knowledge base UUIDs",
+            )
+            .at("dataset_ids"),
+        );
+    }
+
+    // Check datasets exist on the target instance, when declared in the config
+    if let (Some(ids), Some(known)) = (dataset_ids, &ctx.config.knowledge.dataset_ids) {
+        for (i, id) in ids.iter().enumerate() {
+            if let Some(id) = id.as_str() {
+                if !known.iter().any(|k| k == id) {
+                    errors.push(
+                        LintError::with_hint(
+                            &rules::KNOWLEDGE_UNKNOWN_DATASET,
+                            node_id,
+                            node_title,
+                            &format!("Unknown dataset ID: {}", id),
+                            "Re-link the knowledge base or add the ID to knowledge.dataset_ids",
+                        )
+                        .at(format!("dataset_ids/{}", i)),
+                    );
+                }
+            }
+        }
     }
 
     // Check retrieval_mode
     let retrieval_mode = data.extra.get("retrieval_mode").and_then(|v| v.as_str());
     match retrieval_mode {
         None => {
-            errors.push(LintError::warning_with_hint(
-                node_id,
-                node_title,
-                "Knowledge Retrieval missing 'retrieval_mode'",
-                "Add: retrieval_mode: single or multiple",
-            ));
+            errors.push(
+                LintError::with_hint(
+                    &rules::KNOWLEDGE_MISSING_RETRIEVAL_MODE,
+                    node_id,
+                    node_title,
+                    "Knowledge Retrieval missing 'retrieval_mode'",
+                    "Add: retrieval_mode: single or multiple",
+                )
+                .at("retrieval_mode"),
+            );
         }
         Some(mode) if mode != "single" && mode != "multiple" => {
-            errors.push(LintError::warning(
-                node_id,
-                node_title,
-                &format!("Unknown retrieval_mode: {} (expected single or multiple)", mode),
-            ));
+            errors.push(
+                LintError::new(
+                    &rules::KNOWLEDGE_UNKNOWN_RETRIEVAL_MODE,
+                    node_id,
+                    node_title,
+                    &format!(
+                        "Unknown retrieval_mode: {} (expected single or multiple)",
+                        mode
+                    ),
+                )
+                .at("retrieval_mode"),
+            );
         }
         _ => {}
     }
@@ -718,12 +1215,16 @@ fn check_knowledge_retrieval_node(
     if let Some(settings) = data.extra.get("single_retrieval_config") {
         if let Some(model) = settings.get("reranking_model") {
             if model.get("provider").and_then(|p| p.as_str()) == Some("openai") {
-                errors.push(LintError::warning_with_hint(
-                    node_id,
-                    node_title,
-                    "Rerank model uses OpenAI - may fail without OpenAI plugin",
-                    "Consider using weighted_score mode instead of rerank",
-                ));
+                errors.push(
+                    LintError::with_hint(
+                        &rules::KNOWLEDGE_OPENAI_RERANK,
+                        node_id,
+                        node_title,
+                        "Rerank model uses OpenAI - may fail without OpenAI plugin",
+                        "Consider using weighted_score mode instead of rerank",
+                    )
+                    .at("single_retrieval_config/reranking_model"),
+                );
             }
         }
     }
@@ -731,15 +1232,36 @@ fn check_knowledge_retrieval_node(
     if let Some(settings) = data.extra.get("multiple_retrieval_config") {
         if let Some(model) = settings.get("reranking_model") {
             if model.get("provider").and_then(|p| p.as_str()) == Some("openai") {
-                errors.push(LintError::warning_with_hint(
-                    node_id,
-                    node_title,
-                    "Rerank model uses OpenAI - may fail without OpenAI plugin",
-                    "Consider using weighted_score mode instead of rerank",
-                ));
+                errors.push(
+                    LintError::with_hint(
+                        &rules::KNOWLEDGE_OPENAI_RERANK,
+                        node_id,
+                        node_title,
+                        "Rerank model uses OpenAI - may fail without OpenAI plugin",
+                        "Consider using weighted_score mode instead of rerank",
+                    )
+                    .at("multiple_retrieval_config/reranking_model"),
+                );
             }
         }
     }
 
     errors
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_series_are_not_similar_classes() {
+        assert!(is_class_series("Plan A", "Plan B"));
+        assert!(is_class_series("Tier 1", "Tier 2"));
+        assert!(is_class_series("Tier1", "Tier2"));
+        assert!(is_class_series("level 9", "Level 10"));
+        assert!(!is_class_series("Refund", "Refunds"));
+        assert!(!is_class_series("Billing issue", "Billing issues"));
+        assert!(!is_class_series("Plan A", "Plan A"));
+        assert!(!is_class_series("1", "2"));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::rules;
+use crate::schema::{has_output, node_outputs, suggest};
+use crate::types::{LintContext, LintError, Node, VARIABLE_SCOPES};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// A `{{#node_id.variable#}}` reference inside text
+pub(crate) static TEMPLATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{#([^#]+)#\}\}").unwrap());
+
+/// How a variable is referenced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RefKind {
+    /// A `[node_id, variable, ...]` selector array
+    Selector,
+    /// A `{{#node_id.variable#}}` template inside a string
+    Template,
+}
+
+/// A variable reference found in node data
+pub(crate) struct VariableRef {
+    /// Node ID, or a variable scope such as `sys`, `conversation` or `env`
+    pub node: String,
+    pub variable: String,
+    pub kind: RefKind,
+    /// Path relative to the referencing node's data
+    pub path: String,
+}
+
+/// Every selector and template reference in a node's data
+pub(crate) fn node_references(node: &Node) -> Vec<VariableRef> {
+    let mut refs = Vec::new();
+    let value = node
+        .data
+        .as_ref()
+        .and_then(|d| serde_json::to_value(d).ok());
+    if let Some(value) = value {
+        collect_references(&value, "", "", &mut refs);
+    }
+    refs
+}
+
+/// What a reference points at
+pub(crate) enum Target<'a> {
+    /// A `sys`, `conversation` or `env` variable, checked by the scope checks
+    Scope,
+    /// No node has the referenced ID
+    MissingNode,
+    /// A declared output, or any output of a node whose outputs are not known
+    Output(&'a Node),
+    /// An output the node does not have, with the outputs it does have
+    UnknownOutput(&'a Node, Vec<String>),
+}
+
+impl VariableRef {
+    /// Resolve the reference against the graph
+    pub fn resolve<'a>(&self, ctx: &'a LintContext) -> Target<'a> {
+        if VARIABLE_SCOPES.contains(&self.node.as_str()) {
+            return Target::Scope;
+        }
+        let Some(node) = ctx.get_node(&self.node) else {
+            return Target::MissingNode;
+        };
+        match node_outputs(node) {
+            Some(outputs) if !has_output(node, &outputs, &self.variable) => {
+                Target::UnknownOutput(node, outputs)
+            }
+            _ => Target::Output(node),
+        }
+    }
+
+    /// reference-node-missing error for a reference that resolved to [`Target::MissingNode`]
+    pub fn missing_node_error(&self, node_id: &str, node_title: &str) -> LintError {
+        LintError::with_hint(
+            &rules::REFERENCE_NODE_MISSING,
+            node_id,
+            node_title,
+            &format!("References non-existent node: {}", self.node),
+            match self.kind {
+                RefKind::Selector => "Check node ID or use sys.query / conversation.var",
+                RefKind::Template => {
+                    "Check node ID or use {{#sys.query#}} / {{#conversation.var#}}"
+                }
+            },
+        )
+        .at(&self.path)
+    }
+
+    /// reference-output-missing error for a reference that resolved to [`Target::UnknownOutput`]
+    pub fn unknown_output_error(
+        &self,
+        ref_node: &Node,
+        outputs: &[String],
+        node_id: &str,
+        node_title: &str,
+    ) -> LintError {
+        let ref_title = ref_node
+            .data
+            .as_ref()
+            .and_then(|d| d.title.as_deref())
+            .unwrap_or(&self.node);
+        let hint = match suggest(&self.variable, outputs.iter().map(String::as_str)) {
+            Some(name) => format!("Did you mean '{}'?", name),
+            None if outputs.is_empty() => format!("'{}' has no output variables", ref_title),
+            None => format!("Available outputs: {}", outputs.join(", ")),
+        };
+
+        LintError::with_hint(
+            &rules::REFERENCE_OUTPUT_MISSING,
+            node_id,
+            node_title,
+            &format!(
+                "References unknown output '{}' of node '{}' ({})",
+                self.variable,
+                ref_title,
+                ref_node.node_type()
+            ),
+            &hint,
+        )
+        .at(&self.path)
+    }
+}
+
+/// Find variable selectors and `{{#node.var#}}` template references in node data
+fn collect_references(value: &Value, path: &str, key: &str, out: &mut Vec<VariableRef>) {
+    let child_path = |segment: &str| {
+        if path.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}/{}", path, segment)
+        }
+    };
+
+    match value {
+        Value::Array(items) if key.ends_with("selector") || key == "query" => {
+            push_selector(items, path, out);
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let item_path = child_path(&i.to_string());
+                match item {
+                    // Variable Aggregator: `variables` is a list of selectors
+                    Value::Array(selector) if key == "variables" => {
+                        push_selector(selector, &item_path, out)
+                    }
+                    _ => collect_references(item, &item_path, "", out),
+                }
+            }
+        }
+        Value::Object(obj) => {
+            // Tool parameters, assigner items and loop variables: {type: variable, value: [node, var]}
+            let is_variable = ["type", "input_type", "value_type"]
+                .iter()
+                .any(|k| obj.get(*k).and_then(Value::as_str) == Some("variable"));
+
+            for (k, v) in obj {
+                let v_path = child_path(&k.replace('~', "~0").replace('/', "~1"));
+                match v {
+                    Value::Array(selector) if is_variable && k == "value" => {
+                        push_selector(selector, &v_path, out)
+                    }
+                    _ => collect_references(v, &v_path, k, out),
+                }
+            }
+        }
+        Value::String(text) => {
+            for cap in TEMPLATE_RE.captures_iter(text) {
+                let mut parts = cap[1].split('.');
+                if let (Some(node), Some(variable)) = (parts.next(), parts.next()) {
+                    if !node.is_empty() {
+                        out.push(VariableRef {
+                            node: node.to_string(),
+                            variable: variable.to_string(),
+                            kind: RefKind::Template,
+                            path: path.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_selector(items: &[Value], path: &str, out: &mut Vec<VariableRef>) {
+    let parts: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+    if parts.len() < 2 || parts.len() != items.len() || parts[0].is_empty() {
+        return;
+    }
+    out.push(VariableRef {
+        node: parts[0].to_string(),
+        variable: parts[1].to_string(),
+        kind: RefKind::Selector,
+        path: path.to_string(),
+    });
+}
@@ -0,0 +1,249 @@
+use crate::rules;
+use crate::source::escape_segment;
+use crate::types::{DifyDsl, LintError, NODES_PATH};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Known credential formats, most specific first
+const SECRET_PATTERNS: &[(&str, &str)] = &[
+    ("Anthropic API key", r"\bsk-ant-[A-Za-z0-9_-]{20,}"),
+    (
+        "OpenAI API key",
+        r"\bsk-(?:proj-|svcacct-)?[A-Za-z0-9_-]{20,}",
+    ),
+    ("AWS access key ID", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    ("Tavily API key", r"\btvly-[A-Za-z0-9_-]{20,}"),
+    ("GitHub token", r"\bgh[pousr]_[A-Za-z0-9]{36,}"),
+    ("Google API key", r"\bAIza[0-9A-Za-z_-]{35}"),
+    ("Slack token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}"),
+    ("Bearer token", r"(?i)\bbearer\s+[A-Za-z0-9._~+/=-]{20,}"),
+];
+
+/// Keys whose values are credentials; the key must end with the credential word, so
+/// fields such as `credential_id`, `token_type` or `secret_name` do not match
+const SECRET_KEY_PATTERN: &str = r"(?i)^[\w-]*?(?:api[_-]?key|secret|token|password|passwd|access[_-]?key|private[_-]?key|credentials?)$";
+
+/// Credential assignments in code and text, e.g. `API_KEY = "..."`
+const SECRET_ASSIGNMENT_PATTERN: &str = r#"(?i)[\w-]*(?:api[_-]?key|secret|token|password|passwd)[\w-]*["']?\s*[:=]\s*["']([^"'\s]{16,})["']"#;
+
+/// Path of the workflow's environment variables
+const ENV_PATH: &str = "/workflow/environment_variables";
+
+/// Literals shorter than this are not treated as possible secrets
+const MIN_SECRET_LEN: usize = 16;
+
+/// Shannon entropy (bits per character) above which a literal looks random
+const MIN_ENTROPY: f64 = 3.5;
+
+struct Scanner {
+    patterns: Vec<(&'static str, Regex)>,
+    secret_key: Regex,
+    assignment: Regex,
+    /// Node ID and title by node index
+    nodes: HashMap<usize, (String, String)>,
+    /// Values already reported, e.g. secret environment variables
+    skip: HashSet<String>,
+    errors: Vec<LintError>,
+}
+
+/// Scan every value in the DSL, including unmodelled fields, for credentials
+pub fn check_secrets(dsl: &DifyDsl) -> Vec<LintError> {
+    let Ok(value) = serde_json::to_value(dsl) else {
+        return Vec::new();
+    };
+
+    let mut scanner = Scanner::new(&value);
+    scanner.check_environment_variables(dsl);
+    scanner.walk(&value, "", "");
+    scanner.errors
+}
+
+/// Scan a document that does not parse as a DSL, so a broken file cannot hide a credential
+pub fn check_raw_secrets(value: &Value) -> Vec<LintError> {
+    let mut scanner = Scanner::new(value);
+    scanner.walk(value, "", "");
+    scanner.errors
+}
+
+impl Scanner {
+    fn new(value: &Value) -> Self {
+        let nodes = value
+            .pointer(NODES_PATH)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, node)| {
+                let field = |pointer: &str| node.pointer(pointer).and_then(Value::as_str);
+                let id = field("/id").unwrap_or("").to_string();
+                let title = field("/data/title")
+                    .or(field("/data/type"))
+                    .unwrap_or("")
+                    .to_string();
+                (i, (id, title))
+            })
+            .collect();
+
+        Self {
+            patterns: SECRET_PATTERNS
+                .iter()
+                .map(|(name, pattern)| (*name, Regex::new(pattern).unwrap()))
+                .collect(),
+            secret_key: Regex::new(SECRET_KEY_PATTERN).unwrap(),
+            assignment: Regex::new(SECRET_ASSIGNMENT_PATTERN).unwrap(),
+            nodes,
+            skip: HashSet::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Secret environment variables must be exported without their values
+    fn check_environment_variables(&mut self, dsl: &DifyDsl) {
+        let vars = dsl
+            .workflow
+            .as_ref()
+            .and_then(|w| w.environment_variables.as_ref())
+            .into_iter()
+            .flatten();
+
+        for (i, var) in vars.enumerate() {
+            let is_secret = var.value_type.as_deref() == Some("secret");
+            let value = var.value.as_ref().and_then(Value::as_str).unwrap_or("");
+            if !is_secret || value.is_empty() {
+                continue;
+            }
+
+            let name = var.name.as_deref().unwrap_or("");
+            let path = format!("{}/{}/value", ENV_PATH, i);
+            self.skip.insert(path.clone());
+            self.errors.push(
+                LintError::with_hint(
+                    &rules::HARDCODED_SECRET,
+                    "",
+                    &format!("env:{}", name),
+                    &format!(
+                        "Secret environment variable '{}' was exported with its value",
+                        name
+                    ),
+                    "Export the app without secrets",
+                )
+                .at(path),
+            );
+        }
+    }
+
+    fn walk(&mut self, value: &Value, path: &str, key: &str) {
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    self.walk(v, &format!("{}/{}", path, escape_segment(k)), k);
+                }
+            }
+            Value::Array(items) => {
+                for (i, v) in items.iter().enumerate() {
+                    self.walk(v, &format!("{}/{}", path, i), key);
+                }
+            }
+            Value::String(s) if !self.skip.contains(path) => self.check_string(s, path, key),
+            _ => {}
+        }
+    }
+
+    fn check_string(&mut self, text: &str, path: &str, key: &str) {
+        let known = self
+            .patterns
+            .iter()
+            .find_map(|(name, re)| Some((*name, re.find(text)?.as_str())));
+        if let Some((name, secret)) = known {
+            let hint = if path.starts_with(ENV_PATH) {
+                "Change the environment variable's type to 'secret' and export without secrets"
+            } else {
+                "Move it to a 'secret' environment variable and reference it as {{#env.NAME#}}"
+            };
+            self.push(
+                &rules::HARDCODED_SECRET,
+                path,
+                format!("{} found: {}", name, redact(secret)),
+                hint,
+            );
+            return;
+        }
+
+        let literal = if self.secret_key.is_match(key) && looks_random(text) {
+            Some(text)
+        } else {
+            self.assignment
+                .captures_iter(text)
+                .filter_map(|c| c.get(1))
+                .map(|m| m.as_str())
+                .find(|s| looks_random(s))
+        };
+        if let Some(literal) = literal {
+            self.push(
+                &rules::POSSIBLE_SECRET,
+                path,
+                format!("Possible credential: {}", redact(literal)),
+                "If this is a credential, move it to a 'secret' environment variable",
+            );
+        }
+    }
+
+    fn push(&mut self, rule: &'static rules::Rule, path: &str, message: String, hint: &str) {
+        let (node_id, title) = self.node_at(path);
+        self.errors
+            .push(LintError::with_hint(rule, &node_id, &title, &message, hint).at(path));
+    }
+
+    /// Node owning a path, or the top-level section for paths outside the graph
+    fn node_at(&self, path: &str) -> (String, String) {
+        let node = path
+            .strip_prefix(NODES_PATH)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|rest| rest.split('/').next())
+            .and_then(|i| i.parse::<usize>().ok())
+            .and_then(|i| self.nodes.get(&i));
+
+        match node {
+            Some((id, title)) => (id.clone(), title.clone()),
+            None => {
+                let section = path.split('/').nth(1).unwrap_or("");
+                (String::new(), section.to_string())
+            }
+        }
+    }
+}
+
+/// Whether a literal looks like a random token rather than a word, sentence or reference
+fn looks_random(text: &str) -> bool {
+    let text = text.trim();
+    if text.chars().count() < MIN_SECRET_LEN
+        || text.contains(char::is_whitespace)
+        || text.contains("{{")
+    {
+        return false;
+    }
+    entropy(text) >= MIN_ENTROPY
+}
+
+/// Shannon entropy of a string in bits per character
+fn entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let len = text.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Show only the start of a secret so reports do not leak it
+fn redact(secret: &str) -> String {
+    let prefix: String = secret.chars().take(6).collect();
+    format!("{}…", prefix)
+}
@@ -0,0 +1,169 @@
+use crate::rules;
+use crate::schema::IDENTIFIER_RE;
+use crate::types::{LintError, NodeData};
+use serde_json::Value;
+
+/// Input types a start node variable can have
+const START_VARIABLE_TYPES: &[&str] = &[
+    "text-input",
+    "paragraph",
+    "select",
+    "number",
+    "checkbox",
+    "file",
+    "file-list",
+    "json",
+    "json_object",
+];
+
+/// Input types whose `max_length` limits the entered text
+const TEXT_TYPES: &[&str] = &["text-input", "paragraph"];
+
+/// Check start node input variables
+pub fn check_start_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let mut declared: Vec<&str> = Vec::new();
+
+    let variables = data.variables.as_ref().and_then(Value::as_array);
+    for (i, var) in variables.into_iter().flatten().enumerate() {
+        let path = format!("variables/{}", i);
+        let name = var.get("variable").and_then(Value::as_str).unwrap_or("");
+        let var_type = var.get("type").and_then(Value::as_str).unwrap_or("");
+        let label = var.get("label").and_then(Value::as_str).unwrap_or("");
+        let mut invalid = |message: String, field: &str| {
+            let path = match field {
+                "" => path.clone(),
+                f => format!("{}/{}", path, f),
+            };
+            errors.push(
+                LintError::new(
+                    &rules::START_VARIABLE_INVALID,
+                    node_id,
+                    node_title,
+                    &message,
+                )
+                .at(path),
+            );
+        };
+
+        if name.is_empty() {
+            invalid(format!("Start variable {} has no 'variable' name", i), "");
+        } else if declared.contains(&name) {
+            invalid(format!("Duplicate start variable: {}", name), "variable");
+        } else if !IDENTIFIER_RE.is_match(name) {
+            invalid(
+                format!(
+                    "Start variable name '{}' must start with a letter or underscore and \
+                     contain only letters, digits and underscores",
+                    name
+                ),
+                "variable",
+            );
+        }
+        if !name.is_empty() && !declared.contains(&name) {
+            declared.push(name);
+        }
+
+        if var_type.is_empty() {
+            invalid(format!("Start variable '{}' has no 'type'", name), "");
+        } else if !START_VARIABLE_TYPES.contains(&var_type) {
+            invalid(
+                format!("Start variable '{}' has unknown type '{}'", name, var_type),
+                "type",
+            );
+        }
+
+        if var.get("required").is_some_and(|r| !r.is_boolean()) {
+            invalid(
+                format!("Start variable '{}' has a non-boolean 'required'", name),
+                "required",
+            );
+        }
+
+        let default = var
+            .get("default")
+            .and_then(Value::as_str)
+            .filter(|d| !d.is_empty());
+        match var.get("max_length").filter(|m| !m.is_null()) {
+            Some(m) if m.as_u64().is_none_or(|n| n == 0) => invalid(
+                format!(
+                    "Start variable '{}' max_length must be a positive integer, got {}",
+                    name, m
+                ),
+                "max_length",
+            ),
+            Some(m) if TEXT_TYPES.contains(&var_type) => {
+                let max = m.as_u64().unwrap_or(0) as usize;
+                if default.is_some_and(|d| d.chars().count() > max) {
+                    invalid(
+                        format!(
+                            "Start variable '{}' default is longer than its max_length of {}",
+                            name, max
+                        ),
+                        "default",
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        if var_type == "select" {
+            errors.extend(check_select(node_id, node_title, var, name, default, &path));
+        }
+
+        if label.trim().is_empty() {
+            errors.push(
+                LintError::with_hint(
+                    &rules::START_VARIABLE_MISSING_LABEL,
+                    node_id,
+                    node_title,
+                    &format!("Start variable '{}' has no label", name),
+                    "Add a label; users see it as the input's name",
+                )
+                .at(path),
+            );
+        }
+    }
+
+    errors
+}
+
+/// Select inputs need options, and their default must be one of them
+fn check_select(
+    node_id: &str,
+    node_title: &str,
+    var: &Value,
+    name: &str,
+    default: Option<&str>,
+    path: &str,
+) -> Vec<LintError> {
+    let options: Vec<&str> = var
+        .get("options")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    let (message, field) = if options.iter().all(|o| o.trim().is_empty()) {
+        (
+            format!("Select variable '{}' has no options", name),
+            "options",
+        )
+    } else if let Some(d) = default.filter(|d| !options.contains(d)) {
+        (
+            format!(
+                "Select variable '{}' default '{}' is not one of its options",
+                name, d
+            ),
+            "default",
+        )
+    } else {
+        return Vec::new();
+    };
+
+    vec![
+        LintError::new(&rules::START_SELECT_INVALID, node_id, node_title, &message)
+            .at(format!("{}/{}", path, field)),
+    ]
+}
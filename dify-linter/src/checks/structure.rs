@@ -1,4 +1,6 @@
-use crate::types::{DifyDsl, LintError, Node};
+use crate::checks::graph::is_entry;
+use crate::rules;
+use crate::types::{DifyDsl, LintError, MODEL_CONFIG_MODES, NODES_PATH, WORKFLOW_MODES};
 
 /// Check basic DSL structure
 pub fn check_basic_structure(dsl: &DifyDsl) -> Vec<LintError> {
@@ -6,14 +8,54 @@ pub fn check_basic_structure(dsl: &DifyDsl) -> Vec<LintError> {
 
     // Check app section
     if dsl.app.is_none() {
-        errors.push(LintError::error("", "root", "Missing 'app' section"));
+        errors
+            .push(LintError::new(&rules::MISSING_APP, "", "root", "Missing 'app' section").at(""));
+    }
+
+    // Check app mode
+    if let Some(mode) = dsl.mode() {
+        if !MODEL_CONFIG_MODES.contains(&mode) && !WORKFLOW_MODES.contains(&mode) {
+            errors.push(
+                LintError::new(
+                    &rules::UNKNOWN_APP_MODE,
+                    "",
+                    "app",
+                    &format!("Unknown app mode: {}", mode),
+                )
+                .at("/app/mode"),
+            );
+        }
+    }
+
+    // Chat, agent and completion apps have no workflow graph
+    if dsl.uses_model_config() {
+        if dsl.model_config.is_none() {
+            errors.push(
+                LintError::new(
+                    &rules::MISSING_MODEL_CONFIG,
+                    "",
+                    "root",
+                    "Missing 'model_config' section",
+                )
+                .at(""),
+            );
+        }
+        return errors;
     }
 
     // Check workflow section
     let workflow = match &dsl.workflow {
         Some(w) => w,
         None => {
-            errors.push(LintError::error("", "root", "Missing 'workflow' section"));
+            errors.push(
+                LintError::new(
+                    &rules::MISSING_WORKFLOW,
+                    "",
+                    "root",
+                    "Missing 'workflow' section",
+                )
+                .at(""),
+            );
             return errors;
         }
     };
@@ -22,7 +64,15 @@ pub fn check_basic_structure(dsl: &DifyDsl) -> Vec<LintError> {
     let graph = match &workflow.graph {
         Some(g) => g,
         None => {
-            errors.push(LintError::error("", "workflow", "Missing 'graph' section"));
+            errors.push(
+                LintError::new(
+                    &rules::MISSING_GRAPH,
+                    "",
+                    "workflow",
+                    "Missing 'graph' section",
+                )
+                .at("/workflow"),
+            );
             return errors;
         }
     };
@@ -31,27 +81,86 @@ pub fn check_basic_structure(dsl: &DifyDsl) -> Vec<LintError> {
     let nodes = match &graph.nodes {
         Some(n) if !n.is_empty() => n,
         _ => {
-            errors.push(LintError::error("", "graph", "No nodes defined"));
+            errors.push(
+                LintError::new(&rules::NO_NODES, "", "graph", "No nodes defined")
+                    .at("/workflow/graph"),
+            );
             return errors;
         }
     };
 
     // Check for start node
-    let start_nodes: Vec<&Node> = nodes
+    let start_nodes: Vec<usize> = nodes
         .iter()
-        .filter(|n| {
+        .enumerate()
+        .filter(|(_, n)| {
             n.data
                 .as_ref()
                 .and_then(|d| d.node_type.as_ref())
                 .map(|t| t == "start")
                 .unwrap_or(false)
         })
+        .map(|(i, _)| i)
         .collect();
 
+    // Trigger-only workflows start from their triggers
+    let has_entry = nodes.iter().any(|n| is_entry(n.node_type()));
     match start_nodes.len() {
-        0 => errors.push(LintError::error("", "graph", "Missing start node")),
-        1 => {}
-        _ => errors.push(LintError::error("", "graph", "Multiple start nodes found")),
+        0 if !has_entry => errors.push(
+            LintError::new(
+                &rules::MISSING_START_NODE,
+                "",
+                "graph",
+                "Missing start node",
+            )
+            .at(NODES_PATH),
+        ),
+        0 | 1 => {}
+        _ => errors.push(
+            LintError::new(
+                &rules::MULTIPLE_START_NODES,
+                "",
+                "graph",
+                "Multiple start nodes found",
+            )
+            .at(format!("{}/{}", NODES_PATH, start_nodes[1])),
+        ),
+    }
+
+    // Workflow apps finish at End nodes, chatflows reply with Answer nodes
+    let (wanted, other) = match dsl.mode() {
+        Some("workflow") => ("end", "answer"),
+        Some("advanced-chat") => ("answer", "end"),
+        _ => return errors,
+    };
+    let mode = dsl.mode().unwrap_or("");
+
+    if !nodes.iter().any(|n| n.node_type() == wanted) {
+        errors.push(
+            LintError::with_hint(
+                &rules::MISSING_OUTPUT_NODE,
+                "",
+                "graph",
+                &format!("{} app has no '{}' node", mode, wanted),
+                &format!("Add an '{}' node at the end of each branch", wanted),
+            )
+            .at(NODES_PATH),
+        );
+    }
+    for (i, node) in nodes.iter().enumerate() {
+        if node.node_type() == other {
+            let title = node.data.as_ref().and_then(|d| d.title.as_deref());
+            errors.push(
+                LintError::with_hint(
+                    &rules::WRONG_OUTPUT_NODE,
+                    node.id.as_deref().unwrap_or(""),
+                    title.unwrap_or(other),
+                    &format!("'{}' nodes cannot be used in a {} app", other, mode),
+                    &format!("Replace it with an '{}' node", wanted),
+                )
+                .at(format!("{}/{}", NODES_PATH, i)),
+            );
+        }
     }
 
     errors
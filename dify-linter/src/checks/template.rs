@@ -0,0 +1,298 @@
+use crate::rules;
+use crate::schema::suggest;
+use crate::types::{LintError, NodeData};
+use minijinja::Environment;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Filters built into Jinja2, which Dify uses to render templates
+const JINJA2_FILTERS: &[&str] = &[
+    "abs",
+    "attr",
+    "batch",
+    "capitalize",
+    "center",
+    "count",
+    "d",
+    "default",
+    "dictsort",
+    "e",
+    "escape",
+    "filesizeformat",
+    "first",
+    "float",
+    "forceescape",
+    "format",
+    "groupby",
+    "indent",
+    "int",
+    "items",
+    "join",
+    "last",
+    "length",
+    "list",
+    "lower",
+    "map",
+    "max",
+    "min",
+    "pprint",
+    "random",
+    "reject",
+    "rejectattr",
+    "replace",
+    "reverse",
+    "round",
+    "safe",
+    "select",
+    "selectattr",
+    "slice",
+    "sort",
+    "string",
+    "striptags",
+    "sum",
+    "title",
+    "tojson",
+    "trim",
+    "truncate",
+    "unique",
+    "upper",
+    "urlencode",
+    "urlize",
+    "wordcount",
+    "wordwrap",
+    "xmlattr",
+];
+
+/// Globals Jinja2 provides to every template
+const JINJA2_GLOBALS: &[&str] = &["range", "dict", "lipsum", "cycler", "joiner", "namespace"];
+
+/// Check a template-transform node's Jinja2 template against its declared variables
+pub fn check_template_node(node_id: &str, node_title: &str, data: &NodeData) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    // Check declared variables
+    let mut declared: Vec<(usize, &str)> = Vec::new();
+    let variables = data.variables.as_ref().and_then(Value::as_array);
+    for (i, var) in variables.into_iter().flatten().enumerate() {
+        let path = format!("variables/{}", i);
+        let name = var.get("variable").and_then(Value::as_str).unwrap_or("");
+        let has_selector = var
+            .get("value_selector")
+            .and_then(Value::as_array)
+            .is_some_and(|s| !s.is_empty());
+
+        let message = if name.is_empty() {
+            Some(format!("Template variable {} has no 'variable' name", i))
+        } else if declared.iter().any(|(_, d)| *d == name) {
+            Some(format!("Duplicate template variable: {}", name))
+        } else if !has_selector {
+            Some(format!(
+                "Template variable '{}' has no 'value_selector'",
+                name
+            ))
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            errors.push(
+                LintError::new(
+                    &rules::TEMPLATE_VARIABLE_INVALID,
+                    node_id,
+                    node_title,
+                    &message,
+                )
+                .at(path),
+            );
+        }
+        if !name.is_empty() && !declared.iter().any(|(_, d)| *d == name) {
+            declared.push((i, name));
+        }
+    }
+
+    let Some(template) = data.extra.get("template").and_then(Value::as_str) else {
+        errors.push(
+            LintError::new(
+                &rules::TEMPLATE_INVALID,
+                node_id,
+                node_title,
+                "Template-transform node missing 'template'",
+            )
+            .at("template"),
+        );
+        return errors;
+    };
+
+    let env = Environment::new();
+    let parsed = match env.template_from_str(template) {
+        Ok(t) => t,
+        Err(e) => {
+            let detail = e.detail().unwrap_or("invalid syntax");
+            let message = match e.line() {
+                Some(line) => format!("Template syntax error on line {}: {}", line, detail),
+                None => format!("Template syntax error: {}", detail),
+            };
+            errors.push(
+                LintError::new(&rules::TEMPLATE_INVALID, node_id, node_title, &message)
+                    .at("template"),
+            );
+            return errors;
+        }
+    };
+
+    // Check filters
+    let mut reported = HashSet::new();
+    for filter in template_filters(template) {
+        if !JINJA2_FILTERS.contains(&filter) && reported.insert(filter) {
+            let mut err = LintError::new(
+                &rules::TEMPLATE_UNKNOWN_FILTER,
+                node_id,
+                node_title,
+                &format!("Unknown Jinja2 filter: {}", filter),
+            );
+            if let Some(s) = suggest(filter, JINJA2_FILTERS.iter().copied()) {
+                err.fix_hint = Some(format!("Did you mean '{}'?", s));
+            }
+            errors.push(err.at("template"));
+        }
+    }
+
+    // Compare used and declared variables
+    let mut used: Vec<String> = parsed.undeclared_variables(false).into_iter().collect();
+    used.sort();
+    for name in &used {
+        let is_declared = declared.iter().any(|(_, d)| d == name);
+        if is_declared || JINJA2_GLOBALS.contains(&name.as_str()) {
+            continue;
+        }
+        let hint = match suggest(name, declared.iter().map(|(_, d)| *d)) {
+            Some(s) => format!("Did you mean '{}'? Otherwise declare it in variables", s),
+            None => format!("Add '{}' to the node's variables", name),
+        };
+        errors.push(
+            LintError::with_hint(
+                &rules::TEMPLATE_UNDECLARED_VARIABLE,
+                node_id,
+                node_title,
+                &format!("Template uses undeclared variable: {}", name),
+                &hint,
+            )
+            .at("template"),
+        );
+    }
+
+    for (i, name) in declared {
+        if !used.iter().any(|u| u == name) {
+            errors.push(
+                LintError::new(
+                    &rules::TEMPLATE_UNUSED_VARIABLE,
+                    node_id,
+                    node_title,
+                    &format!("Template variable '{}' is never used", name),
+                )
+                .at(format!("variables/{}", i)),
+            );
+        }
+    }
+
+    errors
+}
+
+/// Names of the filters applied in a template, in order of appearance
+fn template_filters(template: &str) -> Vec<&str> {
+    let mut filters = Vec::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        let close = match rest[open + 1..].chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            Some('#') => "#}",
+            _ => {
+                rest = &rest[open + 1..];
+                continue;
+            }
+        };
+        let body_start = open + 2;
+
+        // Comments may contain unbalanced quotes, so only tags skip string literals
+        if close == "#}" {
+            match rest[body_start..].find(close) {
+                Some(len) => rest = &rest[body_start + len + 2..],
+                None => break,
+            }
+            continue;
+        }
+
+        let Some(len) = find_close(&rest[body_start..], close) else {
+            break;
+        };
+        let body = &rest[body_start..body_start + len];
+        rest = &rest[body_start + len + 2..];
+
+        let words: Vec<&str> = body
+            .trim_matches(|c: char| c == '-' || c == '+' || c.is_whitespace())
+            .split_whitespace()
+            .collect();
+        match words.as_slice() {
+            // Raw blocks are output verbatim
+            ["raw"] => match rest.find("endraw") {
+                Some(end) => rest = &rest[end..],
+                None => break,
+            },
+            ["filter", name, ..] if close == "%}" => {
+                filters.push(name.split('|').next().unwrap_or(name));
+                filters.extend(pipe_filters(body));
+            }
+            _ => filters.extend(pipe_filters(body)),
+        }
+    }
+
+    filters
+}
+
+/// Offset of `close` in a tag body, skipping string literals
+fn find_close(body: &str, close: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(_) if c == '\\' => {
+                chars.next();
+            }
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if body[i..].starts_with(close) => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Filter names following '|' in a tag body, outside string literals
+fn pipe_filters(body: &str) -> Vec<&str> {
+    let mut filters = Vec::new();
+    let mut quote = None;
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(_) if c == '\\' => {
+                chars.next();
+            }
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '|' => {
+                let after = body[i + 1..].trim_start();
+                let len = after
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(after.len());
+                if len > 0 {
+                    filters.push(&after[..len]);
+                }
+            }
+            None => {}
+        }
+    }
+    filters
+}
@@ -0,0 +1,456 @@
+use crate::rules;
+use crate::types::{ConversationVariable, EnvironmentVariable, LintError, Node, NodeData};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A Dify variable type, as written in `value_type`, `output_type` or code `outputs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VarType {
+    String,
+    Number,
+    Boolean,
+    Object,
+    File,
+    ArrayString,
+    ArrayNumber,
+    ArrayBoolean,
+    ArrayObject,
+    ArrayFile,
+    /// An array whose element type is not known
+    ArrayAny,
+}
+
+impl VarType {
+    /// Parse a Dify type name; `any` and unknown names have no type
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "string" | "secret" | "select" | "paragraph" | "text-input" => Self::String,
+            "number" | "integer" => Self::Number,
+            "boolean" | "bool" | "checkbox" => Self::Boolean,
+            "object" | "json" | "json_object" => Self::Object,
+            "file" => Self::File,
+            "array[string]" => Self::ArrayString,
+            "array[number]" => Self::ArrayNumber,
+            "array[boolean]" | "array[bool]" => Self::ArrayBoolean,
+            "array[object]" => Self::ArrayObject,
+            "array[file]" | "file-list" => Self::ArrayFile,
+            "array" | "array[any]" => Self::ArrayAny,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Object => "object",
+            Self::File => "file",
+            Self::ArrayString => "array[string]",
+            Self::ArrayNumber => "array[number]",
+            Self::ArrayBoolean => "array[boolean]",
+            Self::ArrayObject => "array[object]",
+            Self::ArrayFile => "array[file]",
+            Self::ArrayAny => "array",
+        }
+    }
+
+    pub fn is_array(self) -> bool {
+        matches!(
+            self,
+            Self::ArrayString
+                | Self::ArrayNumber
+                | Self::ArrayBoolean
+                | Self::ArrayObject
+                | Self::ArrayFile
+                | Self::ArrayAny
+        )
+    }
+
+    /// Whether a value of one type can stand in for the other; arrays with an unknown
+    /// element type fit any array
+    pub fn compatible(self, other: Self) -> bool {
+        self == other
+            || (self.is_array() && other.is_array())
+                && (self == Self::ArrayAny || other == Self::ArrayAny)
+    }
+
+    /// Element type of an array
+    pub fn element(self) -> Option<Self> {
+        match self {
+            Self::ArrayString => Some(Self::String),
+            Self::ArrayNumber => Some(Self::Number),
+            Self::ArrayBoolean => Some(Self::Boolean),
+            Self::ArrayObject => Some(Self::Object),
+            Self::ArrayFile => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    /// Array of this type; nested arrays have no element type
+    pub fn array_of(self) -> Self {
+        match self {
+            Self::String => Self::ArrayString,
+            Self::Number => Self::ArrayNumber,
+            Self::Boolean => Self::ArrayBoolean,
+            Self::Object => Self::ArrayObject,
+            Self::File => Self::ArrayFile,
+            _ => Self::ArrayAny,
+        }
+    }
+
+    /// Comparison operators Dify offers for a variable of this type
+    fn operators(self) -> &'static [&'static str] {
+        match self {
+            Self::String => &[
+                "contains",
+                "not contains",
+                "start with",
+                "end with",
+                "is",
+                "is not",
+                "in",
+                "not in",
+            ],
+            Self::Number => &["=", "≠", ">", "<", "≥", "≤"],
+            Self::Boolean => &["is", "is not"],
+            Self::File => &["exists", "not exists"],
+            Self::Object => &[],
+            Self::ArrayFile => &["contains", "not contains", "all of", "exists", "not exists"],
+            _ => &["contains", "not contains", "all of"],
+        }
+    }
+}
+
+/// Operators that apply to a variable of any type
+const ANY_TYPE_OPERATORS: &[&str] = &["empty", "not empty", "null", "not null"];
+
+/// System variable types
+const SYSTEM_VARIABLE_TYPES: &[(&str, VarType)] = &[
+    ("query", VarType::String),
+    ("files", VarType::ArrayFile),
+    ("user_id", VarType::String),
+    ("app_id", VarType::String),
+    ("workflow_id", VarType::String),
+    ("workflow_run_id", VarType::String),
+    ("conversation_id", VarType::String),
+    ("dialogue_count", VarType::Number),
+];
+
+/// Infers the type of the variable a selector points at
+pub(crate) struct TypeResolver<'a> {
+    nodes: HashMap<&'a str, &'a Node>,
+    conv_vars: &'a [ConversationVariable],
+    env_vars: &'a [EnvironmentVariable],
+}
+
+impl<'a> TypeResolver<'a> {
+    pub fn new(
+        nodes: &'a [Node],
+        conv_vars: &'a [ConversationVariable],
+        env_vars: &'a [EnvironmentVariable],
+    ) -> Self {
+        let nodes = nodes
+            .iter()
+            .filter_map(|n| Some((n.id.as_deref()?, n)))
+            .collect();
+        Self {
+            nodes,
+            conv_vars,
+            env_vars,
+        }
+    }
+
+    /// Type of `[node_id, variable]`, or None if it cannot be known from the DSL
+    pub fn selector_type(&self, selector: &[String]) -> Option<VarType> {
+        self.resolve(selector, 0)
+    }
+
+    fn resolve(&self, selector: &[String], depth: usize) -> Option<VarType> {
+        // Paths into objects (`[node, var, key]`) have no declared type
+        let [source, name] = selector else {
+            return None;
+        };
+        // Bounded, in case iteration and list-operator inputs form a cycle
+        if depth > self.nodes.len() {
+            return None;
+        }
+
+        match source.as_str() {
+            "sys" => SYSTEM_VARIABLE_TYPES
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, t)| *t),
+            "conversation" => self
+                .conv_vars
+                .iter()
+                .find(|v| v.name.as_ref() == Some(name))
+                .and_then(|v| VarType::parse(v.value_type.as_deref()?)),
+            "env" => self
+                .env_vars
+                .iter()
+                .find(|v| v.name.as_ref() == Some(name))
+                .and_then(|v| VarType::parse(v.value_type.as_deref()?)),
+            id => self.output_type(self.nodes.get(id)?, name, depth),
+        }
+    }
+
+    fn output_type(&self, node: &Node, name: &str, depth: usize) -> Option<VarType> {
+        let data = node.data.as_ref()?;
+        let field = |key: &str| data.extra.get(key);
+        let input = |key: &str| {
+            let selector = selector_of(field(key)?)?;
+            self.resolve(&selector, depth + 1)
+        };
+
+        match (node.node_type(), name) {
+            ("start", _) => declared_type(data.variables.as_ref(), "variable", name, "type"),
+            ("llm" | "agent", "text" | "reasoning_content" | "finish_reason") => {
+                Some(VarType::String)
+            }
+            ("llm" | "agent", "usage" | "structured_output") => Some(VarType::Object),
+            ("llm" | "agent", "files") => Some(VarType::ArrayFile),
+            ("agent", "json") => Some(VarType::ArrayObject),
+            ("code", _) => field("outputs")?
+                .get(name)?
+                .get("type")?
+                .as_str()
+                .and_then(VarType::parse),
+            ("http-request", "body") => Some(VarType::String),
+            ("http-request", "status_code") => Some(VarType::Number),
+            ("http-request", "headers") => Some(VarType::Object),
+            ("http-request", "files") => Some(VarType::ArrayFile),
+            ("knowledge-retrieval", "result") => Some(VarType::ArrayObject),
+            ("document-extractor", "text") => {
+                let is_array = field("is_array_file").and_then(Value::as_bool) == Some(true);
+                Some(if is_array {
+                    VarType::ArrayString
+                } else {
+                    VarType::String
+                })
+            }
+            ("template-transform", "output") => Some(VarType::String),
+            ("question-classifier", "class_name" | "class_id") => Some(VarType::String),
+            ("parameter-extractor", "__is_success") => Some(VarType::Number),
+            ("parameter-extractor", "__reason") => Some(VarType::String),
+            ("parameter-extractor", "__usage") => Some(VarType::Object),
+            ("parameter-extractor", _) => declared_type(field("parameters"), "name", name, "type"),
+            ("variable-aggregator" | "variable-assigner", "output") => {
+                VarType::parse(field("output_type")?.as_str()?)
+            }
+            // Grouped aggregators expose one object per group
+            ("variable-aggregator" | "variable-assigner", _) => Some(VarType::Object),
+            ("iteration", "output") => Some(input("output_selector")?.array_of()),
+            ("iteration", "item") => input("iterator_selector")?.element(),
+            ("iteration", "index") => Some(VarType::Number),
+            ("loop", _) => declared_type(field("loop_variables"), "label", name, "var_type"),
+            ("list-operator", "result") => input("variable"),
+            ("list-operator", "first_record" | "last_record") => input("variable")?.element(),
+            ("answer", "answer") => Some(VarType::String),
+            _ => None,
+        }
+    }
+}
+
+/// Type of the item in a list of declarations whose `key` is `name`
+fn declared_type(items: Option<&Value>, key: &str, name: &str, type_key: &str) -> Option<VarType> {
+    items?
+        .as_array()?
+        .iter()
+        .find(|item| item.get(key).and_then(Value::as_str) == Some(name))?
+        .get(type_key)?
+        .as_str()
+        .and_then(VarType::parse)
+}
+
+fn selector_of(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|s| s.as_str().map(str::to_string))
+        .collect()
+}
+
+fn node_title(node: &Node) -> &str {
+    node.data
+        .as_ref()
+        .and_then(|d| d.title.as_deref())
+        .unwrap_or(node.node_type())
+}
+
+/// Check that variables are used where their inferred type fits
+pub fn check_types(
+    nodes: &[Node],
+    conv_vars: &[ConversationVariable],
+    env_vars: &[EnvironmentVariable],
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let resolver = TypeResolver::new(nodes, conv_vars, env_vars);
+
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        let id = node.id.as_deref().unwrap_or("");
+        let title = node_title(node);
+        let Some(data) = &node.data else { continue };
+
+        match node.node_type() {
+            "iteration" => errors.extend(check_iterator(id, title, data, &resolver)),
+            "if-else" => errors.extend(check_conditions(id, title, data, &resolver)),
+            "variable-aggregator" | "variable-assigner" => {
+                errors.extend(check_aggregator(id, title, data, &resolver))
+            }
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// `iterator_selector` must be an array
+fn check_iterator(
+    id: &str,
+    title: &str,
+    data: &NodeData,
+    resolver: &TypeResolver,
+) -> Vec<LintError> {
+    let Some(selector) = data.extra.get("iterator_selector").and_then(selector_of) else {
+        return Vec::new();
+    };
+    match resolver.selector_type(&selector) {
+        Some(t) if !t.compatible(VarType::ArrayAny) => vec![LintError::with_hint(
+            &rules::ITERATION_INPUT_NOT_ARRAY,
+            id,
+            title,
+            &format!(
+                "Iteration input '{}' has type {}, not an array",
+                selector.join("."),
+                t.name()
+            ),
+            "Iterate over an array output, e.g. a code node output of type array[string]",
+        )
+        .at("iterator_selector")],
+        _ => Vec::new(),
+    }
+}
+
+/// Comparison operators must fit the type of the variable they compare
+fn check_conditions(
+    id: &str,
+    title: &str,
+    data: &NodeData,
+    resolver: &TypeResolver,
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    let mut conditions = Vec::new();
+    for (i, case) in data.cases.iter().flatten().enumerate() {
+        for (j, cond) in case.conditions.iter().flatten().enumerate() {
+            conditions.push((format!("cases/{}/conditions/{}", i, j), cond));
+        }
+    }
+    for (i, group) in data.conditions.iter().flatten().enumerate() {
+        for (j, cond) in group.conditions.iter().flatten().enumerate() {
+            conditions.push((format!("conditions/{}/conditions/{}", i, j), cond));
+        }
+    }
+
+    for (path, cond) in conditions {
+        let (Some(selector), Some(op)) = (&cond.variable_selector, &cond.comparison_operator)
+        else {
+            continue;
+        };
+        let Some(t) = resolver.selector_type(selector) else {
+            continue;
+        };
+        if ANY_TYPE_OPERATORS.contains(&op.as_str()) || t.operators().contains(&op.as_str()) {
+            continue;
+        }
+
+        let mut allowed: Vec<&str> = t.operators().to_vec();
+        allowed.extend(ANY_TYPE_OPERATORS.iter().take(2));
+        errors.push(
+            LintError::with_hint(
+                &rules::OPERATOR_TYPE_MISMATCH,
+                id,
+                title,
+                &format!(
+                    "Operator '{}' cannot compare '{}' of type {}",
+                    op,
+                    selector.join("."),
+                    t.name()
+                ),
+                &format!("Use one of: {}", allowed.join(", ")),
+            )
+            .at(format!("{}/comparison_operator", path)),
+        );
+    }
+
+    errors
+}
+
+/// Every aggregated variable must have the declared `output_type`
+fn check_aggregator(
+    id: &str,
+    title: &str,
+    data: &NodeData,
+    resolver: &TypeResolver,
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+
+    let settings = data.extra.get("advanced_settings");
+    let grouped = settings
+        .and_then(|s| s.get("group_enabled"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut groups = Vec::new();
+    if grouped {
+        let items = settings
+            .and_then(|s| s.get("groups"))
+            .and_then(Value::as_array);
+        for (g, group) in items.into_iter().flatten().enumerate() {
+            groups.push((
+                group.get("output_type"),
+                group.get("variables"),
+                format!("advanced_settings/groups/{}/variables", g),
+            ));
+        }
+    } else {
+        groups.push((
+            data.extra.get("output_type"),
+            data.variables.as_ref(),
+            "variables".to_string(),
+        ));
+    }
+
+    for (output_type, variables, path) in groups {
+        let Some(expected) = output_type.and_then(Value::as_str).and_then(VarType::parse) else {
+            continue;
+        };
+        let variables = variables.and_then(Value::as_array).into_iter().flatten();
+        for (i, selector) in variables.enumerate() {
+            let Some(selector) = selector_of(selector) else {
+                continue;
+            };
+            match resolver.selector_type(&selector) {
+                Some(t) if !t.compatible(expected) => errors.push(
+                    LintError::with_hint(
+                        &rules::AGGREGATOR_TYPE_MISMATCH,
+                        id,
+                        title,
+                        &format!(
+                            "'{}' has type {}, but the aggregator outputs {}",
+                            selector.join("."),
+                            t.name(),
+                            expected.name()
+                        ),
+                        "Aggregate variables of a single type",
+                    )
+                    .at(format!("{}/{}", path, i)),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    errors
+}
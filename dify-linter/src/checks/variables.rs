@@ -1,121 +1,264 @@
-use crate::types::{ConversationVariable, LintContext, LintError, Node};
-use regex::Regex;
+use crate::checks::references::Target;
+use crate::fix::Fix;
+use crate::rules;
+use crate::schema::{suggest, CHAT_SYSTEM_VARIABLES, SYSTEM_VARIABLES};
+use crate::types::{ConversationVariable, EnvironmentVariable, LintContext, LintError, Node};
 use serde_json::Value;
+use std::collections::HashSet;
+
+/// Value types an environment variable can have
+const ENVIRONMENT_VARIABLE_TYPES: &[&str] = &["string", "number", "secret"];
 
 /// Check conversation variables have required fields
 pub fn check_conversation_variables(vars: &[ConversationVariable]) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    for cv in vars {
+    for (i, cv) in vars.iter().enumerate() {
         let name = cv.name.as_deref().unwrap_or("unknown");
+        let cv_path = format!("/workflow/conversation_variables/{}", i);
 
         if cv.value_type.is_none() {
-            errors.push(LintError::error_with_hint(
-                "",
-                &format!("cv:{}", name),
-                "Missing 'value_type' in conversation variable",
-                "Add: value_type: string",
-            ));
+            errors.push(
+                LintError::with_hint(
+                    &rules::CONVERSATION_VARIABLE_MISSING_TYPE,
+                    "",
+                    &format!("cv:{}", name),
+                    "Missing 'value_type' in conversation variable",
+                    "Add: value_type: string",
+                )
+                .at(&cv_path)
+                .with_fix(Fix::set(&cv_path, "value_type", "string")),
+            );
         }
 
         if cv.value.is_none() {
-            errors.push(LintError::error_with_hint(
-                "",
-                &format!("cv:{}", name),
-                "Missing 'value' in conversation variable",
-                "Add: value: ''",
-            ));
+            errors.push(
+                LintError::with_hint(
+                    &rules::CONVERSATION_VARIABLE_MISSING_VALUE,
+                    "",
+                    &format!("cv:{}", name),
+                    "Missing 'value' in conversation variable",
+                    "Add: value: ''",
+                )
+                .at(&cv_path)
+                .with_fix(Fix::set(
+                    &cv_path,
+                    "value",
+                    default_value(cv.value_type.as_deref().unwrap_or("string")),
+                )),
+            );
         }
     }
 
     errors
 }
 
-/// Check all variable references in prompts
-pub fn check_variable_references(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
+/// Empty value for a conversation variable type, as YAML
+fn default_value(value_type: &str) -> &'static str {
+    match value_type {
+        "number" => "0",
+        "boolean" => "false",
+        "object" => "{}",
+        t if t.starts_with("array") => "[]",
+        _ => "''",
+    }
+}
+
+/// Check environment variables are well-formed and match the `env.*` references to them
+pub fn check_environment_variables(
+    vars: &[EnvironmentVariable],
+    nodes: &[Node],
+    ctx: &LintContext,
+) -> Vec<LintError> {
     let mut errors = Vec::new();
-    let re = Regex::new(r"\{\{#([^#]+)#\}\}").unwrap();
+    let mut declared: Vec<&str> = Vec::new();
 
-    for node in nodes {
-        let node_id = node.id.as_deref().unwrap_or("");
-        let node_data = match &node.data {
-            Some(d) => d,
-            None => continue,
+    for (i, var) in vars.iter().enumerate() {
+        let name = var.name.as_deref().unwrap_or("");
+        let path = format!("/workflow/environment_variables/{}", i);
+        let title = format!("env:{}", name);
+        let value_type = var.value_type.as_deref().unwrap_or("");
+
+        let is_numeric = match &var.value {
+            Some(Value::Number(_)) | None => true,
+            Some(Value::String(s)) => s.is_empty() || s.trim().parse::<f64>().is_ok(),
+            Some(_) => false,
+        };
+        let message = if name.is_empty() {
+            Some(format!("Environment variable {} has no 'name'", i))
+        } else if declared.contains(&name) {
+            Some(format!("Duplicate environment variable: {}", name))
+        } else if !ENVIRONMENT_VARIABLE_TYPES.contains(&value_type) {
+            Some(format!(
+                "Environment variable '{}' has invalid value_type '{}'",
+                name, value_type
+            ))
+        } else if value_type == "number" && !is_numeric {
+            Some(format!(
+                "Environment variable '{}' is a number but its value is not",
+                name
+            ))
+        } else {
+            None
         };
+        if let Some(message) = message {
+            let mut err =
+                LintError::new(&rules::ENVIRONMENT_VARIABLE_INVALID, "", &title, &message);
+            if !name.is_empty() && !ENVIRONMENT_VARIABLE_TYPES.contains(&value_type) {
+                err.fix_hint = Some(format!(
+                    "Use one of: {}",
+                    ENVIRONMENT_VARIABLE_TYPES.join(", ")
+                ));
+            }
+            errors.push(err.at(&path));
+        }
+        if !name.is_empty() && !declared.contains(&name) {
+            declared.push(name);
+        }
+    }
 
-        let node_type = node_data.node_type.as_deref().unwrap_or("");
-        let node_title = node_data.title.as_deref().unwrap_or(node_type);
+    let mut used = HashSet::new();
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        let node_id = node.id.as_deref().unwrap_or("");
+        let node_title = node
+            .data
+            .as_ref()
+            .and_then(|d| d.title.as_deref())
+            .unwrap_or(node.node_type());
 
-        // Check prompt_template for LLM nodes
-        if node_type == "llm" {
-            if let Some(prompt) = &node_data.prompt_template {
-                errors.extend(check_prompt_references(node_id, node_title, prompt, ctx, &re));
+        for r in ctx.references(node).iter().filter(|r| r.node == "env") {
+            if declared.contains(&r.variable.as_str()) {
+                used.insert(r.variable.as_str());
+                continue;
             }
+            let hint = match suggest(&r.variable, declared.iter().copied()) {
+                Some(s) => format!("Did you mean 'env.{}'?", s),
+                None => format!(
+                    "Declare '{}' in the workflow's environment variables",
+                    r.variable
+                ),
+            };
+            errors.push(
+                LintError::with_hint(
+                    &rules::ENVIRONMENT_VARIABLE_UNDEFINED,
+                    node_id,
+                    node_title,
+                    &format!(
+                        "Reference to undeclared environment variable: env.{}",
+                        r.variable
+                    ),
+                    &hint,
+                )
+                .at(&r.path),
+            );
+        }
+    }
+
+    for (i, var) in vars.iter().enumerate() {
+        let name = var.name.as_deref().unwrap_or("");
+        if !name.is_empty() && !used.contains(name) {
+            errors.push(
+                LintError::new(
+                    &rules::ENVIRONMENT_VARIABLE_UNUSED,
+                    "",
+                    &format!("env:{}", name),
+                    &format!("Environment variable '{}' is never referenced", name),
+                )
+                .at(format!("/workflow/environment_variables/{}", i)),
+            );
         }
     }
 
     errors
 }
 
-/// Check variable references in prompt template
-fn check_prompt_references(
-    node_id: &str,
-    node_title: &str,
-    prompt: &Value,
+/// Check `sys.*` and `conversation.*` references against the app mode and declared variables
+pub fn check_scope_references(
+    nodes: &[Node],
+    conv_vars: &[ConversationVariable],
+    mode: Option<&str>,
     ctx: &LintContext,
-    re: &Regex,
 ) -> Vec<LintError> {
     let mut errors = Vec::new();
+    let is_workflow = mode == Some("workflow");
+    let conv_names: Vec<&str> = conv_vars.iter().filter_map(|v| v.name.as_deref()).collect();
+    let sys_names: Vec<&str> = if is_workflow {
+        SYSTEM_VARIABLES.to_vec()
+    } else {
+        [SYSTEM_VARIABLES, CHAT_SYSTEM_VARIABLES].concat()
+    };
 
-    match prompt {
-        Value::Object(obj) => {
-            if let Some(Value::String(text)) = obj.get("text") {
-                errors.extend(check_text_references(node_id, node_title, text, ctx, re));
-            }
-        }
-        Value::Array(arr) => {
-            for item in arr {
-                if let Value::Object(obj) = item {
-                    if let Some(Value::String(text)) = obj.get("text") {
-                        errors.extend(check_text_references(node_id, node_title, text, ctx, re));
-                    }
-                }
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        let node_id = node.id.as_deref().unwrap_or("");
+        let node_title = node
+            .data
+            .as_ref()
+            .and_then(|d| d.title.as_deref())
+            .unwrap_or(node.node_type());
+
+        let mut seen = HashSet::new();
+        for r in ctx.references(node) {
+            if !seen.insert((r.node.clone(), r.variable.clone(), r.path.clone())) {
+                continue;
             }
+            let name = format!("{}.{}", r.node, r.variable);
+
+            let (rule, message, hint) = match r.node.as_str() {
+                "sys" if is_workflow && CHAT_SYSTEM_VARIABLES.contains(&r.variable.as_str()) => (
+                    &rules::CHAT_ONLY_VARIABLE,
+                    format!("{} is only available in advanced-chat apps", name),
+                    "Add a Start node variable for it instead".to_string(),
+                ),
+                "sys" if !sys_names.contains(&r.variable.as_str()) => (
+                    &rules::SYSTEM_VARIABLE_UNKNOWN,
+                    format!("Unknown system variable: {}", name),
+                    match suggest(&r.variable, sys_names.iter().copied()) {
+                        Some(s) => format!("Did you mean 'sys.{}'?", s),
+                        None => format!("Use one of: sys.{}", sys_names.join(", sys.")),
+                    },
+                ),
+                "conversation" if is_workflow => (
+                    &rules::CHAT_ONLY_VARIABLE,
+                    format!("{} is only available in advanced-chat apps", name),
+                    "Workflow apps have no conversations; use a Start node variable".to_string(),
+                ),
+                "conversation" if !conv_names.contains(&r.variable.as_str()) => (
+                    &rules::CONVERSATION_VARIABLE_UNDEFINED,
+                    format!("Reference to undeclared conversation variable: {}", name),
+                    match suggest(&r.variable, conv_names.iter().copied()) {
+                        Some(s) => format!("Did you mean 'conversation.{}'?", s),
+                        None => format!(
+                            "Declare '{}' in the workflow's conversation variables",
+                            r.variable
+                        ),
+                    },
+                ),
+                _ => continue,
+            };
+            errors
+                .push(LintError::with_hint(rule, node_id, node_title, &message, &hint).at(&r.path));
         }
-        _ => {}
     }
 
     errors
 }
 
-/// Check variable references in text
-fn check_text_references(
-    node_id: &str,
-    node_title: &str,
-    text: &str,
-    ctx: &LintContext,
-    re: &Regex,
-) -> Vec<LintError> {
+/// Check that every selector and template reference names an existing node
+pub fn check_variable_references(nodes: &[Node], ctx: &LintContext) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    for cap in re.captures_iter(text) {
-        if let Some(ref_str) = cap.get(1) {
-            let parts: Vec<&str> = ref_str.as_str().split('.').collect();
-            if let Some(&ref_source) = parts.first() {
-                // Valid system references
-                if ref_source == "sys" || ref_source == "conversation" {
-                    continue;
-                }
-
-                // Check if node exists
-                if !ctx.node_exists(ref_source) {
-                    errors.push(LintError::error_with_hint(
-                        node_id,
-                        node_title,
-                        &format!("References non-existent node: {}", ref_source),
-                        "Check node ID or use sys.query / conversation.var",
-                    ));
-                }
+    for node in nodes.iter().filter(|n| !n.is_note()) {
+        let Some(data) = &node.data else { continue };
+        let node_id = node.id.as_deref().unwrap_or("");
+        let node_title = data.title.as_deref().unwrap_or(node.node_type());
+
+        let mut seen = HashSet::new();
+        for r in ctx.references(node) {
+            if !seen.insert((r.path.clone(), r.node.clone())) {
+                continue;
+            }
+            if let Target::MissingNode = r.resolve(ctx) {
+                errors.push(r.missing_node_error(node_id, node_title));
             }
         }
     }
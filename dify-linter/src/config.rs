@@ -0,0 +1,259 @@
+use crate::rules::{find_rule, Rule};
+use crate::types::{LintError, Severity};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Config file name, discovered upward from the linted file
+pub const CONFIG_FILE_NAME: &str = ".difylint.toml";
+
+/// Errors raised while loading a config file
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Error reading {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Error parsing {path}: {source}")]
+    Toml {
+        path: String,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("Unknown rule '{rule}' in {path}")]
+    UnknownRule { path: String, rule: String },
+
+    #[error("Invalid override path '{pattern}' in {path}: {message}")]
+    Pattern {
+        path: String,
+        pattern: String,
+        message: String,
+    },
+}
+
+/// Level a rule is configured to, overriding its default severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+/// Allowed model names and providers for LLM-backed nodes
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelsConfig {
+    /// Model names, matched case-insensitively as substrings
+    pub allowed: Option<Vec<String>>,
+    /// Model provider IDs, e.g. `langgenius/bedrock/bedrock`
+    pub providers: Option<Vec<String>>,
+}
+
+/// Known tool providers and their tools
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// Provider ID to tool names; an empty list allows any tool
+    pub providers: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Knowledge bases that exist on the target instance
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnowledgeConfig {
+    pub dataset_ids: Option<Vec<String>>,
+}
+
+/// Code node settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CodeConfig {
+    pub languages: Option<Vec<String>>,
+}
+
+/// Loop node settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoopsConfig {
+    /// Highest allowed `loop_count` (the instance's LOOP_NODE_MAX_COUNT)
+    pub max_count: Option<u64>,
+}
+
+/// Settings applied to files under a directory or matching a glob
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Override {
+    /// Directory or glob, relative to the config file
+    pub path: String,
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+    #[serde(default)]
+    pub models: ModelsConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    #[serde(default)]
+    pub knowledge: KnowledgeConfig,
+    #[serde(default)]
+    pub code: CodeConfig,
+    #[serde(default)]
+    pub loops: LoopsConfig,
+}
+
+/// Linter configuration (`.difylint.toml`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Rule code or name to level
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+    #[serde(default)]
+    pub models: ModelsConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    #[serde(default)]
+    pub knowledge: KnowledgeConfig,
+    #[serde(default)]
+    pub code: CodeConfig,
+    #[serde(default)]
+    pub loops: LoopsConfig,
+    #[serde(default)]
+    pub overrides: Vec<Override>,
+}
+
+impl Config {
+    /// Load and validate a config file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let display = path.display().to_string();
+        let content = fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: display.clone(),
+            source: e,
+        })?;
+        let config: Config = toml::from_str(&content).map_err(|e| ConfigError::Toml {
+            path: display.clone(),
+            source: e,
+        })?;
+
+        let rule_keys = config
+            .rules
+            .keys()
+            .chain(config.overrides.iter().flat_map(|o| o.rules.keys()));
+        for key in rule_keys {
+            if find_rule(key).is_none() {
+                return Err(ConfigError::UnknownRule {
+                    path: display,
+                    rule: key.clone(),
+                });
+            }
+        }
+
+        for o in &config.overrides {
+            glob::Pattern::new(&o.path).map_err(|e| ConfigError::Pattern {
+                path: display.clone(),
+                pattern: o.path.clone(),
+                message: e.msg.to_string(),
+            })?;
+        }
+
+        Ok(config)
+    }
+
+    /// Find the nearest config file in the file's directory or its ancestors
+    pub fn discover(file: &Path) -> Option<PathBuf> {
+        let file = file.canonicalize().ok()?;
+        file.ancestors()
+            .skip(1)
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Effective config for a file: the explicit or discovered config with matching overrides
+    pub fn for_file(file: &Path, explicit: Option<&Path>) -> Result<Self, ConfigError> {
+        let config_path = match explicit {
+            Some(p) => p.to_path_buf(),
+            None => match Self::discover(file) {
+                Some(p) => p,
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let config = Self::load(&config_path)?;
+        let base_dir = config_path
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        Ok(config.resolve(&base_dir, file))
+    }
+
+    /// Merge every override whose path matches the file, in declaration order
+    fn resolve(mut self, base_dir: &Path, file: &Path) -> Self {
+        let relative = file
+            .canonicalize()
+            .ok()
+            .and_then(|f| f.strip_prefix(base_dir).ok().map(Path::to_path_buf));
+
+        let overrides = std::mem::take(&mut self.overrides);
+        if let Some(relative) = relative {
+            for o in overrides.into_iter().filter(|o| o.matches(&relative)) {
+                self.rules.extend(o.rules);
+                merge(&mut self.models.allowed, o.models.allowed);
+                merge(&mut self.models.providers, o.models.providers);
+                merge(&mut self.tools.providers, o.tools.providers);
+                merge(&mut self.knowledge.dataset_ids, o.knowledge.dataset_ids);
+                merge(&mut self.code.languages, o.code.languages);
+                merge(&mut self.loops.max_count, o.loops.max_count);
+            }
+        }
+
+        self
+    }
+
+    /// Configured level for a rule, by code or name
+    pub fn rule_level(&self, rule: &Rule) -> Option<RuleLevel> {
+        self.rules
+            .get(rule.code)
+            .or_else(|| self.rules.get(rule.name))
+            .copied()
+    }
+
+    /// Drop disabled rules and apply severity overrides
+    pub fn apply(&self, errors: Vec<LintError>) -> Vec<LintError> {
+        errors
+            .into_iter()
+            .filter_map(|mut err| {
+                let level = find_rule(err.code).and_then(|r| self.rule_level(r));
+                match level {
+                    Some(RuleLevel::Off) => return None,
+                    Some(RuleLevel::Warning) => err.severity = Severity::Warning,
+                    Some(RuleLevel::Error) => err.severity = Severity::Error,
+                    None => {}
+                }
+                Some(err)
+            })
+            .collect()
+    }
+}
+
+impl Override {
+    fn matches(&self, relative: &Path) -> bool {
+        let dir = Path::new(self.path.trim_end_matches('/'));
+        relative.starts_with(dir)
+            || glob::Pattern::new(&self.path)
+                .map(|p| p.matches_path(relative))
+                .unwrap_or(false)
+    }
+}
+
+fn merge<T>(base: &mut Option<T>, value: Option<T>) {
+    if value.is_some() {
+        *base = value;
+    }
+}
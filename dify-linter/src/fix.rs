@@ -0,0 +1,299 @@
+use crate::config::Config;
+use crate::input::{read_source, InputError};
+use crate::linter::lint_source;
+use crate::source::{escape_segment, SourceFile, SourceMap};
+use crate::types::LintError;
+use serde::Serialize;
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors raised while applying fixes to a file
+#[derive(Debug, Error)]
+pub enum FixError {
+    #[error(transparent)]
+    Input(#[from] InputError),
+
+    #[error("Fixed YAML for {path} does not parse, leaving file unchanged: {source}")]
+    Invalid {
+        path: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    #[error("Error writing {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// A machine-applicable fix for a lint error
+#[derive(Debug, Clone, Serialize)]
+pub struct Fix {
+    pub edits: Vec<FixEdit>,
+}
+
+/// Set `key` to `value` in the mapping at `path`, inserting the key if it is missing
+#[derive(Debug, Clone, Serialize)]
+pub struct FixEdit {
+    /// JSON pointer to the mapping, or relative to the node's `data` when it does not start with '/'
+    pub path: String,
+    pub key: String,
+    /// YAML text of the value, e.g. `over-write` or `'2'`
+    pub value: String,
+}
+
+impl Fix {
+    /// Fix consisting of a single key assignment
+    pub fn set(path: impl Into<String>, key: &str, value: &str) -> Self {
+        Self {
+            edits: vec![FixEdit {
+                path: path.into(),
+                key: key.to_string(),
+                value: value.to_string(),
+            }],
+        }
+    }
+}
+
+/// Textual replacement of `start..end` in the YAML
+#[derive(Debug, Clone, PartialEq)]
+struct TextEdit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// Result of fixing a file
+pub struct FixedFile {
+    pub path: PathBuf,
+    pub source: SourceFile,
+    /// Fixed YAML document
+    pub yaml: String,
+    /// Fixed file content, re-wrapped in JSON if the original was
+    pub text: String,
+    /// Number of lint errors fixed
+    pub fixed: usize,
+}
+
+impl FixedFile {
+    /// Overwrite the original file with the fixed content
+    pub fn write(&self) -> Result<(), FixError> {
+        fs::write(&self.path, &self.text).map_err(|e| FixError::Io {
+            path: self.path.display().to_string(),
+            source: e,
+        })
+    }
+
+    /// Unified diff of the YAML document (the decoded `data` for JSON exports)
+    pub fn diff(&self) -> String {
+        let name = self.path.display().to_string();
+        let (old_header, new_header) = if self.source.is_json_wrapped() {
+            (format!("a/{} (data)", name), format!("b/{} (data)", name))
+        } else {
+            (format!("a/{}", name), format!("b/{}", name))
+        };
+
+        TextDiff::from_lines(&self.source.yaml, &self.yaml)
+            .unified_diff()
+            .header(&old_header, &new_header)
+            .to_string()
+    }
+}
+
+/// Lint a file and apply every available fix; returns None if nothing is fixable
+pub fn fix_file(path: &Path, config: Config) -> Result<Option<FixedFile>, FixError> {
+    let source = read_source(path)?;
+    let result = lint_source(&source, config);
+    let errors: Vec<_> = result.errors.iter().chain(&result.warnings).collect();
+
+    let source_map = SourceMap::parse(&source.yaml);
+    let mut edits = Vec::new();
+    let mut fixed = 0;
+
+    for err in errors {
+        if let Some(err_edits) = resolve_fix(err, &source.yaml, &source_map) {
+            edits.extend(err_edits);
+            fixed += 1;
+        }
+    }
+
+    if edits.is_empty() {
+        return Ok(None);
+    }
+
+    let edits = normalize(edits);
+    let yaml = apply(&source.yaml, &edits);
+    serde_yaml::from_str::<serde_yaml::Value>(&yaml).map_err(|e| FixError::Invalid {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+
+    let text = if source.is_json_wrapped() {
+        rewrap(&source, &edits)
+    } else {
+        yaml.clone()
+    };
+
+    Ok(Some(FixedFile {
+        path: path.to_path_buf(),
+        source,
+        yaml,
+        text,
+        fixed,
+    }))
+}
+
+/// Text edits for all of an error's fix, or None if any part cannot be located
+fn resolve_fix(err: &LintError, yaml: &str, map: &SourceMap) -> Option<Vec<TextEdit>> {
+    err.fix
+        .as_ref()?
+        .edits
+        .iter()
+        .map(|edit| resolve_edit(edit, yaml, map))
+        .collect()
+}
+
+fn resolve_edit(edit: &FixEdit, yaml: &str, map: &SourceMap) -> Option<TextEdit> {
+    let key_path = format!("{}/{}", edit.path, escape_segment(&edit.key));
+
+    // Existing key: replace its value, keeping the key and anything before the colon
+    if let Some(span) = map.get(&key_path) {
+        let value_start = map.value_start(&key_path)?;
+        let end = span.start + yaml[span.start..span.end].trim_end().len();
+        return Some(match yaml[span.start..value_start].rfind(':') {
+            Some(colon) => TextEdit {
+                start: span.start + colon + 1,
+                end,
+                text: format!(" {}", edit.value),
+            },
+            None => TextEdit {
+                start: value_start,
+                end,
+                text: edit.value.clone(),
+            },
+        });
+    }
+
+    if !map.is_mapping(&edit.path) {
+        return None;
+    }
+
+    let entry = format!("{}: {}", edit.key, edit.value);
+    let value_start = map.value_start(&edit.path)?;
+
+    // Flow mapping: append before the closing brace
+    if yaml[value_start..].starts_with('{') {
+        let span = map.get(&edit.path)?;
+        let close = span.start + yaml[span.start..span.end].rfind('}')?;
+        let empty = yaml[value_start + 1..close].trim().is_empty();
+        return Some(TextEdit {
+            start: close,
+            end: close,
+            text: if empty { entry } else { format!(", {}", entry) },
+        });
+    }
+
+    // Block mapping: keep keys sorted, as Dify exports them
+    let children = map.children(&edit.path);
+    let (_, first) = children.first()?;
+    let indent = " ".repeat(column(yaml, first.start));
+    let key = escape_segment(&edit.key);
+
+    match children.iter().find(|(k, _)| *k > key.as_str()) {
+        Some((_, next)) => Some(TextEdit {
+            start: next.start,
+            end: next.start,
+            text: format!("{}\n{}", entry, indent),
+        }),
+        None => {
+            let (_, last) = children.last()?;
+            let end = last.start + yaml[last.start..last.end].trim_end().len();
+            Some(TextEdit {
+                start: end,
+                end,
+                text: format!("\n{}{}", indent, entry),
+            })
+        }
+    }
+}
+
+/// Column (in characters) of a byte offset within its line
+fn column(text: &str, offset: usize) -> usize {
+    let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    text[line_start..offset].chars().count()
+}
+
+/// Sort edits, dropping duplicates and any that overlap an earlier edit
+fn normalize(mut edits: Vec<TextEdit>) -> Vec<TextEdit> {
+    edits.sort_by(|a, b| (a.start, a.end, &a.text).cmp(&(b.start, b.end, &b.text)));
+    edits.dedup();
+
+    let mut result = Vec::new();
+    let mut covered = 0;
+    for edit in edits {
+        if edit.start >= covered {
+            covered = edit.end;
+            result.push(edit);
+        }
+    }
+    result
+}
+
+/// Apply sorted, non-overlapping edits to a text
+fn apply(text: &str, edits: &[TextEdit]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    for edit in edits {
+        out.push_str(&text[pos..edit.start]);
+        out.push_str(&edit.text);
+        pos = edit.end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Apply YAML edits directly to the JSON `data` literal so the rest of the file is untouched
+fn rewrap(source: &SourceFile, edits: &[TextEdit]) -> String {
+    let literal = source.data_range.clone().unwrap_or_default();
+    let original = &source.text[literal];
+    let ascii = original.is_ascii();
+    let escape_slash = original.contains("\\/");
+
+    let json_edits: Vec<_> = edits
+        .iter()
+        .map(|edit| TextEdit {
+            start: source.original_offset(edit.start),
+            end: source.original_offset(edit.end),
+            text: escape_json(&edit.text, ascii, escape_slash),
+        })
+        .collect();
+
+    apply(&source.text, &json_edits)
+}
+
+/// Escape text for a JSON string literal, matching the escaping style of the export
+fn escape_json(text: &str, ascii: bool, escape_slash: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '/' if escape_slash => out.push_str("\\/"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 || (ascii && !c.is_ascii()) => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
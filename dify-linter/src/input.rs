@@ -0,0 +1,125 @@
+use crate::source::SourceFile;
+use crate::types::DifyDsl;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File names picked up when recursing into a directory
+const WORKFLOW_FILE_NAMES: &[&str] = &["workflow.yml", "workflow.yaml"];
+
+/// Errors raised while collecting or loading input files
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("Path not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid glob pattern '{pattern}': {message}")]
+    Glob { pattern: String, message: String },
+
+    #[error("Error reading {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Error parsing JSON wrapper: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("JSON file does not contain 'data' field with YAML")]
+    MissingData,
+
+    #[error("Error parsing YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+/// Expand CLI inputs (files, directories and glob patterns) into a sorted list of files
+pub fn collect_files(inputs: &[String]) -> Result<Vec<PathBuf>, InputError> {
+    let mut files = BTreeSet::new();
+
+    for input in inputs {
+        let path = Path::new(input);
+
+        if path.exists() {
+            collect_path(path, &mut files)?;
+        } else if is_glob(input) {
+            let paths = glob::glob(input).map_err(|e| InputError::Glob {
+                pattern: input.clone(),
+                message: e.msg.to_string(),
+            })?;
+            for entry in paths.flatten() {
+                collect_path(&entry, &mut files)?;
+            }
+        } else {
+            return Err(InputError::NotFound(input.clone()));
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+/// Add a single path, recursing into directories for workflow files
+fn collect_path(path: &Path, files: &mut BTreeSet<PathBuf>) -> Result<(), InputError> {
+    if !path.is_dir() {
+        files.insert(path.to_path_buf());
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(path).map_err(|e| InputError::Io {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            collect_path(&entry_path, files)?;
+        } else if is_workflow_file(&entry_path) {
+            files.insert(entry_path);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_workflow_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| WORKFLOW_FILE_NAMES.contains(&n))
+        .unwrap_or(false)
+}
+
+fn is_glob(input: &str) -> bool {
+    input.contains(['*', '?', '['])
+}
+
+/// Read a file, unwrapping the YAML from JSON exports
+pub fn read_source(path: &Path) -> Result<SourceFile, InputError> {
+    let content = fs::read_to_string(path).map_err(|e| InputError::Io {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+
+    // Exports from the Dify API are JSON with the YAML in a 'data' field
+    if content.trim_start().starts_with('{') {
+        let json: Value = serde_json::from_str(&content)?;
+        if !json.get("data").map(|d| d.is_string()).unwrap_or(false) {
+            return Err(InputError::MissingData);
+        }
+        return SourceFile::from_json(content).ok_or(InputError::MissingData);
+    }
+
+    Ok(SourceFile::from_yaml(content))
+}
+
+/// Parse the DSL from a loaded source file
+pub fn parse_dsl(source: &SourceFile) -> Result<DifyDsl, InputError> {
+    Ok(serde_yaml::from_str(&source.yaml)?)
+}
+
+/// Read and parse a DSL file
+pub fn load_dsl(path: &Path) -> Result<DifyDsl, InputError> {
+    parse_dsl(&read_source(path)?)
+}
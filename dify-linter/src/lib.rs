@@ -1,8 +1,19 @@
 pub mod checks;
+pub mod config;
+pub mod fix;
+pub mod input;
 pub mod linter;
 pub mod report;
+pub mod rules;
+pub mod schema;
+pub mod source;
 pub mod types;
 
-pub use linter::DifyLinter;
-pub use report::{print_json, print_report};
-pub use types::{DifyDsl, LintError, LintResult, Severity};
+pub use config::{Config, ConfigError};
+pub use fix::{fix_file, Fix, FixEdit, FixError, FixedFile};
+pub use input::{collect_files, load_dsl, read_source, InputError};
+pub use linter::{lint_file, lint_source, DifyLinter};
+pub use report::{print_json, print_report, print_rule, print_rules, print_sarif};
+pub use rules::{find_rule, Rule, RULES};
+pub use source::{Location, SourceFile, SourceMap};
+pub use types::{DifyDsl, FileLintResult, LintError, LintResult, LintSummary, Severity};
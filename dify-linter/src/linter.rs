@@ -1,18 +1,43 @@
 use crate::checks::{
-    check_basic_structure, check_conversation_variables, check_edges, check_model_config,
-    check_nodes, check_variable_references,
+    check_basic_structure, check_chat_app, check_containers, check_conversation_variables,
+    check_edges, check_end_nodes, check_environment_variables, check_graph, check_model_config,
+    check_nodes, check_output_references, check_raw_secrets, check_reference_order,
+    check_scope_references, check_secrets, check_types, check_variable_references,
 };
-use crate::types::{DifyDsl, LintContext, LintError, LintResult, Severity};
+use crate::config::Config;
+use crate::input::{parse_dsl, read_source, InputError};
+use crate::rules::{self, Rule};
+use crate::source::{SourceFile, SourceMap, Span};
+use crate::types::{DifyDsl, FileLintResult, LintContext, LintError, LintResult, Severity};
+use std::path::Path;
+
+/// Structural rules after which the remaining checks cannot run
+const CRITICAL_RULES: &[&Rule] = &[
+    &rules::MISSING_MODEL_CONFIG,
+    &rules::MISSING_WORKFLOW,
+    &rules::MISSING_GRAPH,
+    &rules::NO_NODES,
+];
 
 /// Dify DSL Linter
 pub struct DifyLinter {
     dsl: DifyDsl,
+    config: Config,
 }
 
 impl DifyLinter {
     /// Create a new linter instance
     pub fn new(dsl: DifyDsl) -> Self {
-        Self { dsl }
+        Self {
+            dsl,
+            config: Config::default(),
+        }
+    }
+
+    /// Use a project configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
     }
 
     /// Run all lint checks and return the result
@@ -22,58 +47,160 @@ impl DifyLinter {
         // Check basic structure
         all_errors.extend(check_basic_structure(&self.dsl));
 
+        // Scan for credentials, whatever shape the rest of the file is in
+        all_errors.extend(check_secrets(&self.dsl));
+
         // Check for critical errors early
-        let has_critical = all_errors.iter().any(|e| {
-            e.message.contains("Missing 'workflow'")
-                || e.message.contains("Missing 'graph'")
-                || e.message.contains("No nodes defined")
-        });
+        let has_critical = all_errors
+            .iter()
+            .any(|e| CRITICAL_RULES.iter().any(|r| r.code == e.code));
 
         if has_critical {
-            return split_errors(all_errors);
+            return split_errors(self.config.apply(all_errors));
+        }
+
+        // Chat, agent and completion apps have their own check suite
+        if self.dsl.uses_model_config() {
+            all_errors.extend(check_chat_app(&self.dsl, &self.config));
+            return split_errors(self.config.apply(all_errors));
         }
 
         // Get workflow components
         let workflow = self.dsl.workflow.as_ref().unwrap();
         let graph = workflow.graph.as_ref().unwrap();
-        let nodes: Vec<_> = graph
-            .nodes
-            .as_ref()
-            .map(|n| n.clone())
-            .unwrap_or_default();
-        let edges: Vec<_> = graph
-            .edges
-            .as_ref()
-            .map(|e| e.clone())
-            .unwrap_or_default();
-        let conv_vars: Vec<_> = workflow
-            .conversation_variables
-            .as_ref()
-            .map(|c| c.clone())
-            .unwrap_or_default();
+        let nodes: Vec<_> = graph.nodes.clone().unwrap_or_default();
+        let edges: Vec<_> = graph.edges.clone().unwrap_or_default();
+        let conv_vars: Vec<_> = workflow.conversation_variables.clone().unwrap_or_default();
+        let env_vars: Vec<_> = workflow.environment_variables.clone().unwrap_or_default();
 
         // Build context
-        let ctx = LintContext::new(&nodes, &edges);
+        let ctx = LintContext::new(&nodes, &edges, self.config);
 
         // Check conversation variables
         all_errors.extend(check_conversation_variables(&conv_vars));
 
+        // Check environment variables and env.* references
+        all_errors.extend(check_environment_variables(&env_vars, &nodes, &ctx));
+
         // Check nodes
         all_errors.extend(check_nodes(&nodes, &ctx));
 
         // Check edges
         all_errors.extend(check_edges(&edges, &ctx));
 
+        // Check reachability
+        all_errors.extend(check_graph(&nodes, &ctx));
+
+        // Check End node outputs
+        all_errors.extend(check_end_nodes(&nodes, &ctx));
+
+        // Check iteration and loop scoping
+        all_errors.extend(check_containers(&nodes, &ctx));
+
         // Check variable references
         all_errors.extend(check_variable_references(&nodes, &ctx));
+        all_errors.extend(check_scope_references(
+            &nodes,
+            &conv_vars,
+            self.dsl.mode(),
+            &ctx,
+        ));
+        all_errors.extend(check_output_references(&nodes, &ctx));
+        all_errors.extend(check_reference_order(&nodes, &ctx));
+
+        // Check variable types
+        all_errors.extend(check_types(&nodes, &conv_vars, &env_vars));
 
         // Check model config
-        all_errors.extend(check_model_config(&nodes));
+        all_errors.extend(check_model_config(&nodes, &ctx));
 
-        split_errors(all_errors)
+        resolve_paths(&mut all_errors, &ctx);
+
+        split_errors(ctx.config.apply(all_errors))
     }
 }
 
+/// Turn node-relative paths into absolute JSON pointers, defaulting to the node itself
+fn resolve_paths(errors: &mut [LintError], ctx: &LintContext) {
+    for err in errors {
+        let node_path = if err.node_id.is_empty() {
+            String::new()
+        } else {
+            ctx.node_path(&err.node_id)
+        };
+
+        err.path = match err.path.take() {
+            Some(p) if p.is_empty() || p.starts_with('/') => Some(p),
+            Some(p) => Some(format!("{}/data/{}", node_path, p)),
+            None => Some(node_path.clone()),
+        };
+
+        // Relative fix paths point into the node's data; empty means the data itself
+        if let Some(fix) = &mut err.fix {
+            for edit in &mut fix.edits {
+                if !edit.path.starts_with('/') {
+                    edit.path = match edit.path.as_str() {
+                        "" => format!("{}/data", node_path),
+                        p => format!("{}/data/{}", node_path, p),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Load and lint a single file, reporting load failures as errors
+pub fn lint_file(path: &Path, config: Config) -> FileLintResult {
+    let result = match read_source(path) {
+        Ok(source) => lint_source(&source, config),
+        Err(e) => split_errors(vec![LintError::new(
+            &rules::PARSE_ERROR,
+            "",
+            "file",
+            &e.to_string(),
+        )]),
+    };
+
+    FileLintResult {
+        file: path.display().to_string(),
+        result,
+    }
+}
+
+/// Lint a loaded source file and attach source locations to every error
+pub fn lint_source(source: &SourceFile, config: Config) -> LintResult {
+    let mut result = match parse_dsl(source) {
+        Ok(dsl) => DifyLinter::new(dsl).with_config(config).lint(),
+        Err(e) => {
+            let mut err = LintError::new(&rules::PARSE_ERROR, "", "file", &e.to_string());
+            if let InputError::Yaml(yaml_err) = &e {
+                err.location = yaml_err.location().map(|loc| {
+                    source.location(Span {
+                        start: loc.index(),
+                        end: loc.index(),
+                    })
+                });
+            }
+
+            // Still scan whatever YAML there is for credentials
+            let mut errors = vec![err];
+            if let Ok(value) = serde_yaml::from_str(&source.yaml) {
+                errors.extend(check_raw_secrets(&value));
+            }
+            split_errors(config.apply(errors))
+        }
+    };
+    let source_map = SourceMap::parse(&source.yaml);
+
+    for err in result.errors.iter_mut().chain(result.warnings.iter_mut()) {
+        if let Some(span) = err.path.as_deref().and_then(|p| source_map.lookup(p)) {
+            err.location = Some(source.location(span));
+        }
+    }
+
+    result
+}
+
 fn split_errors(all_errors: Vec<LintError>) -> LintResult {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
@@ -1,7 +1,8 @@
-use clap::Parser;
-use dify_linter::{print_json, print_report, DifyDsl, DifyLinter};
-use serde_json::Value;
-use std::fs;
+use clap::{Parser, ValueEnum};
+use dify_linter::{
+    collect_files, find_rule, fix_file, lint_file, print_json, print_report, print_rule,
+    print_rules, print_sarif, Config, LintSummary,
+};
 use std::path::PathBuf;
 use std::process::ExitCode;
 
@@ -11,73 +12,130 @@ use std::process::ExitCode;
 #[command(about = "Validate Dify DSL YAML files before import")]
 #[command(version)]
 struct Args {
-    /// YAML file to lint
-    file: PathBuf,
+    /// YAML files, directories (searched for workflow.yml) or glob patterns to lint
+    #[arg(required_unless_present_any = ["list_rules", "explain"])]
+    paths: Vec<String>,
 
-    /// Output as JSON
-    #[arg(long)]
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Output as JSON (same as --format json)
+    #[arg(long, conflicts_with = "format")]
     json: bool,
 
-    /// Auto-fix issues (coming soon)
+    /// Apply machine-applicable fixes in place, then report remaining issues
     #[arg(long)]
     fix: bool,
+
+    /// Print fixes as a unified diff without changing any file
+    #[arg(long, conflicts_with = "fix")]
+    fix_dry_run: bool,
+
+    /// Only report leaked credentials, as errors, so the run fails if any are found
+    #[arg(long)]
+    deny_secrets: bool,
+
+    /// Config file to use instead of discovering .difylint.toml
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// List all lint rules and exit
+    #[arg(long)]
+    list_rules: bool,
+
+    /// Show the description of a rule (by code or name) and exit
+    #[arg(long, value_name = "CODE")]
+    explain: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    /// SARIF 2.1.0, for code-scanning integrations
+    Sarif,
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
+    let format = if args.json { Format::Json } else { args.format };
+
+    if args.list_rules {
+        print_rules();
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(code) = &args.explain {
+        return match find_rule(code) {
+            Some(rule) => {
+                print_rule(rule);
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("Error: unknown rule '{}'", code);
+                ExitCode::FAILURE
+            }
+        };
+    }
 
-    // Read file
-    let content = match fs::read_to_string(&args.file) {
-        Ok(c) => c,
+    // Collect files
+    let files = match collect_files(&args.paths) {
+        Ok(f) => f,
         Err(e) => {
-            eprintln!("Error reading file: {}", e);
+            eprintln!("Error: {}", e);
             return ExitCode::FAILURE;
         }
     };
 
-    // Detect and extract YAML content
-    let yaml_content = if content.trim_start().starts_with('{') {
-        // JSON format - try to extract 'data' field which contains YAML
-        match serde_json::from_str::<Value>(&content) {
-            Ok(json) => {
-                if let Some(data) = json.get("data").and_then(|d| d.as_str()) {
-                    data.to_string()
-                } else {
-                    eprintln!("Error: JSON file does not contain 'data' field with YAML");
-                    return ExitCode::FAILURE;
-                }
-            }
+    if files.is_empty() {
+        eprintln!("Error: no workflow files found");
+        return ExitCode::FAILURE;
+    }
+
+    // Run linter on every file
+    let mut results = Vec::new();
+    for file in &files {
+        let config = match Config::for_file(file, args.config.as_deref()) {
+            Ok(c) => c,
             Err(e) => {
-                eprintln!("Error parsing JSON wrapper: {}", e);
+                eprintln!("Error: {}", e);
                 return ExitCode::FAILURE;
             }
-        }
-    } else {
-        content
-    };
+        };
 
-    // Parse YAML
-    let dsl: DifyDsl = match serde_yaml::from_str(&yaml_content) {
-        Ok(d) => d,
-        Err(e) => {
-            eprintln!("Error parsing YAML: {}", e);
-            return ExitCode::FAILURE;
+        if args.fix || args.fix_dry_run {
+            match fix_file(file, config.clone()) {
+                // Keep machine-readable output on stdout parseable
+                Ok(Some(fixed)) if args.fix_dry_run && format != Format::Text => {
+                    eprint!("{}", fixed.diff())
+                }
+                Ok(Some(fixed)) if args.fix_dry_run => print!("{}", fixed.diff()),
+                Ok(Some(fixed)) => match fixed.write() {
+                    Ok(()) => eprintln!("Fixed {} issues in {}", fixed.fixed, file.display()),
+                    Err(e) => eprintln!("Error: {}", e),
+                },
+                Ok(None) => {}
+                Err(e) => eprintln!("Error: {}", e),
+            }
         }
-    };
 
-    // Run linter
-    let linter = DifyLinter::new(dsl);
-    let result = linter.lint();
+        let mut result = lint_file(file, config);
+        if args.deny_secrets {
+            result.result = result.result.deny_secrets();
+        }
+        results.push(result);
+    }
 
     // Output
-    if args.json {
-        print_json(&result);
-    } else {
-        print_report(&result);
+    match format {
+        Format::Text => print_report(&results),
+        Format::Json => print_json(&results),
+        Format::Sarif => print_sarif(&results),
     }
 
     // Exit code
-    if result.valid {
+    if LintSummary::from_results(&results).valid() {
         ExitCode::SUCCESS
     } else {
         ExitCode::FAILURE
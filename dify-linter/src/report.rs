@@ -1,20 +1,48 @@
-use crate::types::{LintError, LintResult, Severity};
+use crate::rules::{Rule, RULES};
+use crate::types::{FileLintResult, LintError, LintResult, LintSummary, Severity};
 use colored::*;
+use serde::Serialize;
+use serde_json::{json, Value};
 
-/// Print lint report to stdout
-pub fn print_report(result: &LintResult) {
+/// SARIF schema referenced by `print_sarif` output
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// JSON document for a batch run
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    valid: bool,
+    files: &'a [FileLintResult],
+    summary: LintSummary,
+}
+
+/// Print lint report for all files to stdout
+pub fn print_report(results: &[FileLintResult]) {
     println!("{}", "=".repeat(60));
     println!("DIFY DSL LINT REPORT");
     println!("{}", "=".repeat(60));
 
+    for (i, file_result) in results.iter().enumerate() {
+        if i > 0 {
+            println!("{}", "-".repeat(60));
+        }
+        println!("\n{}", file_result.file.bold());
+        print_file_report(&file_result.file, &file_result.result);
+    }
+
+    let summary = LintSummary::from_results(results);
+    println!("{}", "=".repeat(60));
+    println!(
+        "Total: {} files ({} failed), {} errors, {} warnings",
+        summary.files, summary.failed_files, summary.errors, summary.warnings
+    );
+    println!("{}", "=".repeat(60));
+}
+
+fn print_file_report(file: &str, result: &LintResult) {
     if !result.errors.is_empty() {
-        println!(
-            "\n{} ({}):\n",
-            "ERRORS".red().bold(),
-            result.errors.len()
-        );
+        println!("\n{} ({}):\n", "ERRORS".red().bold(), result.errors.len());
         for err in &result.errors {
-            print_lint_error(err);
+            print_lint_error(file, err);
         }
     }
 
@@ -25,7 +53,7 @@ pub fn print_report(result: &LintResult) {
             result.warnings.len()
         );
         for warn in &result.warnings {
-            print_lint_error(warn);
+            print_lint_error(file, warn);
         }
     }
 
@@ -33,33 +61,185 @@ pub fn print_report(result: &LintResult) {
         println!("\n{}\n", "No issues found!".green().bold());
     }
 
-    println!("{}", "=".repeat(60));
     println!(
-        "Summary: {} errors, {} warnings",
+        "Summary: {} errors, {} warnings\n",
         result.errors.len(),
         result.warnings.len()
     );
-    println!("{}", "=".repeat(60));
 }
 
-fn print_lint_error(err: &LintError) {
+fn print_lint_error(file: &str, err: &LintError) {
     let prefix = match err.severity {
         Severity::Error => "x".red(),
         Severity::Warning => "!".yellow(),
     };
 
     println!("  {} [{}] {}", prefix, err.node_id, err.node_title);
-    println!("    -> {}", err.message);
+    if let Some(loc) = &err.location {
+        println!("    {} {}:{}:{}", "at".dimmed(), file, loc.line, loc.column);
+    }
+    println!(
+        "    -> {} {}",
+        err.message,
+        format!("[{}]", err.code).dimmed()
+    );
     if let Some(hint) = &err.fix_hint {
-        println!("    {} Fix: {}", "=>".cyan(), hint);
+        let fixable = if err.fix.is_some() {
+            " (fixable with --fix)"
+        } else {
+            ""
+        };
+        println!("    {} Fix: {}{}", "=>".cyan(), hint, fixable.dimmed());
     }
     println!();
 }
 
-/// Print result as JSON
-pub fn print_json(result: &LintResult) {
-    match serde_json::to_string_pretty(result) {
+/// Print results for all files as JSON
+pub fn print_json(results: &[FileLintResult]) {
+    let summary = LintSummary::from_results(results);
+    let report = JsonReport {
+        valid: summary.valid(),
+        files: results,
+        summary,
+    };
+
+    match serde_json::to_string_pretty(&report) {
         Ok(json) => println!("{}", json),
         Err(e) => eprintln!("Error serializing result: {}", e),
     }
 }
+
+/// Print results for all files as a SARIF 2.1.0 log, for code-scanning tools
+pub fn print_sarif(results: &[FileLintResult]) {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.code,
+                "name": rule.name,
+                "shortDescription": { "text": rule.summary },
+                "fullDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": severity_label(rule.default_severity) },
+                "properties": { "category": rule.category.as_str() },
+            })
+        })
+        .collect();
+
+    let sarif_results: Vec<Value> = results
+        .iter()
+        .flat_map(|r| {
+            r.result
+                .errors
+                .iter()
+                .chain(&r.result.warnings)
+                .map(move |err| sarif_result(&r.file, err))
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "dify-linter",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": sarif_results,
+        }],
+    });
+
+    match serde_json::to_string_pretty(&log) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Error serializing result: {}", e),
+    }
+}
+
+fn sarif_result(file: &str, err: &LintError) -> Value {
+    let mut text = match (err.node_id.is_empty(), err.node_title.is_empty()) {
+        (true, _) => err.message.clone(),
+        (false, true) => format!("[{}] {}", err.node_id, err.message),
+        (false, false) => format!("[{}] {}: {}", err.node_id, err.node_title, err.message),
+    };
+    if let Some(hint) = &err.fix_hint {
+        text.push_str(&format!("\nFix: {}", hint));
+    }
+
+    // Code scanning needs a region; file-level errors point at the first line
+    let region = match &err.location {
+        Some(loc) => json!({ "startLine": loc.line, "startColumn": loc.column }),
+        None => json!({ "startLine": 1 }),
+    };
+
+    json!({
+        "ruleId": err.code,
+        "ruleIndex": RULES.iter().position(|r| r.code == err.code),
+        "level": severity_label(err.severity),
+        "message": { "text": text },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": file_uri(file) },
+                "region": region,
+            }
+        }],
+        "properties": {
+            "nodeId": err.node_id,
+            "path": err.path,
+        },
+    })
+}
+
+/// Relative URI reference for a file path, percent-encoding everything but unreserved characters
+fn file_uri(file: &str) -> String {
+    let path = file.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let mut uri = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Print all registered rules
+pub fn print_rules() {
+    for rule in RULES {
+        println!(
+            "{}  {:<8} {:<10} {:<40} {}",
+            rule.code.bold(),
+            severity_label(rule.default_severity),
+            rule.category.as_str(),
+            rule.name,
+            rule.summary
+        );
+    }
+}
+
+/// Print the long description of a rule
+pub fn print_rule(rule: &Rule) {
+    println!("{} {}", rule.code.bold(), rule.name.bold());
+    println!();
+    println!("  Category: {}", rule.category.as_str());
+    println!(
+        "  Default severity: {}",
+        severity_label(rule.default_severity)
+    );
+    println!();
+    println!("  {}", rule.summary);
+    println!();
+    println!("  {}", rule.description);
+}
+
+/// Lowercase severity name, also a valid SARIF `level`
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
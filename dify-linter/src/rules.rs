@@ -0,0 +1,1365 @@
+use crate::types::Severity;
+use serde::Serialize;
+
+/// Rule category, used to group rules in listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    File,
+    Structure,
+    Graph,
+    Node,
+    Variable,
+    Model,
+    App,
+    Security,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::File => "file",
+            Category::Structure => "structure",
+            Category::Graph => "graph",
+            Category::Node => "node",
+            Category::Variable => "variable",
+            Category::Model => "model",
+            Category::App => "app",
+            Category::Security => "security",
+        }
+    }
+}
+
+/// A lint rule with a stable code
+#[derive(Debug, Serialize)]
+pub struct Rule {
+    /// Stable code, e.g. `DL1001`
+    pub code: &'static str,
+    /// Stable kebab-case name, e.g. `edge-target-missing`
+    pub name: &'static str,
+    pub category: Category,
+    pub default_severity: Severity,
+    /// One-line summary
+    pub summary: &'static str,
+    /// Long description shown by `--explain`
+    pub description: &'static str,
+}
+
+// DL0xxx: file loading
+
+pub const PARSE_ERROR: Rule = Rule {
+    code: "DL0001",
+    name: "parse-error",
+    category: Category::File,
+    default_severity: Severity::Error,
+    summary: "File could not be read or parsed",
+    description: "The file could not be read, the JSON export wrapper has no 'data' string, \
+        or the YAML does not parse as a Dify DSL document. No other rules run on the file.",
+};
+
+// DL1xxx: graph and edges
+
+pub const EDGE_TARGET_MISSING: Rule = Rule {
+    code: "DL1001",
+    name: "edge-target-missing",
+    category: Category::Graph,
+    default_severity: Severity::Error,
+    summary: "Edge target node does not exist",
+    description: "An edge's 'target' names a node ID that is not in the graph. Dify drops \
+        or rejects such edges on import, disconnecting the downstream part of the workflow.",
+};
+
+pub const EDGE_SOURCE_MISSING: Rule = Rule {
+    code: "DL1002",
+    name: "edge-source-missing",
+    category: Category::Graph,
+    default_severity: Severity::Error,
+    summary: "Edge source node does not exist",
+    description: "An edge's 'source' names a node ID that is not in the graph. This usually \
+        happens when a node was deleted by hand without removing its edges.",
+};
+
+pub const ORPHAN_NODE: Rule = Rule {
+    code: "DL1003",
+    name: "orphan-node",
+    category: Category::Graph,
+    default_severity: Severity::Warning,
+    summary: "Node has no incoming or outgoing edges",
+    description: "The node is not connected to anything and never runs. It is usually left \
+        over from editing; delete it or connect it. Sticky notes are not checked.",
+};
+
+pub const UNREACHABLE_NODE: Rule = Rule {
+    code: "DL1004",
+    name: "unreachable-node",
+    category: Category::Graph,
+    default_severity: Severity::Warning,
+    summary: "Node cannot be reached from the start node",
+    description: "No path of edges leads from the start node (or a trigger node) to this \
+        node, so it never runs. Nodes inside an iteration or loop are reachable through \
+        their container.",
+};
+
+pub const DEAD_END_NODE: Rule = Rule {
+    code: "DL1005",
+    name: "dead-end-node",
+    category: Category::Graph,
+    default_severity: Severity::Warning,
+    summary: "Branch stops without reaching an End or Answer node",
+    description: "The node has no outgoing edges and is not an End or Answer node, so the \
+        branch through it finishes without producing output. The last node inside an \
+        iteration or loop is not a dead end.",
+};
+
+pub const GRAPH_CYCLE: Rule = Rule {
+    code: "DL1006",
+    name: "graph-cycle",
+    category: Category::Graph,
+    default_severity: Severity::Error,
+    summary: "Edges form a cycle",
+    description: "The edges form a loop, which Dify rejects on import or runs forever. \
+        Repetition must use an Iteration or Loop node instead of a back edge. Nodes inside \
+        a container are checked within their own container.",
+};
+
+pub const EDGE_INVALID_SOURCE_HANDLE: Rule = Rule {
+    code: "DL1007",
+    name: "edge-invalid-source-handle",
+    category: Category::Graph,
+    default_severity: Severity::Error,
+    summary: "Edge sourceHandle does not match a branch of its source node",
+    description: "Edges leaving an IF/ELSE node must use a case_id or 'false', edges leaving \
+        a Question Classifier must use a class id, 'fail-branch' edges need the node's \
+        error_strategy set to 'fail-branch', and all other edges use 'source'. Dify does not \
+        follow an edge whose handle matches no branch.",
+};
+
+pub const EDGE_CROSSES_CONTAINER: Rule = Rule {
+    code: "DL1008",
+    name: "edge-crosses-container",
+    category: Category::Graph,
+    default_severity: Severity::Error,
+    summary: "Edge crosses an iteration or loop boundary",
+    description: "The edge connects a node inside an iteration or loop to a node outside it, \
+        or to a node in a different container. The body of a container only runs through \
+        its start node; connect the container node itself instead.",
+};
+
+pub const PARALLEL_END_NODES: Rule = Rule {
+    code: "DL1009",
+    name: "parallel-end-nodes",
+    category: Category::Graph,
+    default_severity: Severity::Warning,
+    summary: "Parallel branches finish at different End nodes",
+    description: "A node starts parallel branches that can reach different End nodes. The \
+        run stops at whichever End node is reached first, so the workflow's outputs depend \
+        on timing.",
+};
+
+// DL2xxx: document structure
+
+pub const MISSING_APP: Rule = Rule {
+    code: "DL2001",
+    name: "missing-app",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "Missing 'app' section",
+    description: "Every DSL export has a top-level 'app' section with the app name and mode. \
+        Dify refuses to import a file without it.",
+};
+
+pub const MISSING_WORKFLOW: Rule = Rule {
+    code: "DL2002",
+    name: "missing-workflow",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "Missing 'workflow' section",
+    description: "Workflow and chatflow apps keep their graph in a top-level 'workflow' \
+        section. Without it there is nothing to lint and the remaining rules are skipped.",
+};
+
+pub const MISSING_GRAPH: Rule = Rule {
+    code: "DL2003",
+    name: "missing-graph",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "Missing 'graph' section",
+    description: "The 'workflow' section has no 'graph' with nodes and edges. The remaining \
+        rules are skipped.",
+};
+
+pub const NO_NODES: Rule = Rule {
+    code: "DL2004",
+    name: "no-nodes",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "Graph has no nodes",
+    description: "The graph's 'nodes' list is missing or empty. A workflow needs at least a \
+        start node. The remaining rules are skipped.",
+};
+
+pub const MISSING_START_NODE: Rule = Rule {
+    code: "DL2005",
+    name: "missing-start-node",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "No start node",
+    description: "The graph has no node of type 'start', so the workflow has no entry point.",
+};
+
+pub const MULTIPLE_START_NODES: Rule = Rule {
+    code: "DL2006",
+    name: "multiple-start-nodes",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "More than one start node",
+    description: "The graph has several nodes of type 'start'. Dify supports exactly one \
+        entry point per workflow.",
+};
+
+pub const MISSING_MODEL_CONFIG: Rule = Rule {
+    code: "DL2007",
+    name: "missing-model-config",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "Missing 'model_config' section",
+    description: "Chat, agent-chat and completion apps keep their model, prompt and inputs in \
+        a top-level 'model_config' section instead of a workflow graph. Without it there is \
+        nothing to lint and the remaining rules are skipped.",
+};
+
+pub const UNKNOWN_APP_MODE: Rule = Rule {
+    code: "DL2008",
+    name: "unknown-app-mode",
+    category: Category::Structure,
+    default_severity: Severity::Warning,
+    summary: "Unknown app mode",
+    description: "app.mode is not one of workflow, advanced-chat, chat, agent-chat or \
+        completion. The file is linted by whether it has a 'workflow' or a 'model_config' \
+        section.",
+};
+
+pub const MISSING_OUTPUT_NODE: Rule = Rule {
+    code: "DL2009",
+    name: "missing-output-node",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "No End or Answer node for the app mode",
+    description: "A workflow app returns its result from End nodes and an advanced-chat app \
+        replies from Answer nodes. The graph has none of the kind its mode needs.",
+};
+
+pub const WRONG_OUTPUT_NODE: Rule = Rule {
+    code: "DL2010",
+    name: "wrong-output-node",
+    category: Category::Structure,
+    default_severity: Severity::Error,
+    summary: "End or Answer node does not fit the app mode",
+    description: "Answer nodes only exist in advanced-chat apps and End nodes only in workflow \
+        apps. Dify rejects the other kind on import.",
+};
+
+// DL3xxx: node configuration
+
+pub const NODE_MISSING_DATA: Rule = Rule {
+    code: "DL3001",
+    name: "node-missing-data",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Node has no 'data'",
+    description: "A node has no 'data' mapping, so its type and configuration are unknown.",
+};
+
+pub const NODE_MISSING_ID: Rule = Rule {
+    code: "DL3002",
+    name: "node-missing-id",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Node has no 'id'",
+    description: "A node has no 'id'. Edges and variable references cannot point to it.",
+};
+
+pub const NODE_MISSING_TYPE: Rule = Rule {
+    code: "DL3003",
+    name: "node-missing-type",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Node has no 'type'",
+    description: "A node's 'data' has no 'type', so Dify cannot tell which kind of node it is.",
+};
+
+pub const LLM_MISSING_MODEL: Rule = Rule {
+    code: "DL3101",
+    name: "llm-missing-model",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "LLM node has no model",
+    description: "An 'llm' node has no 'model' configuration and cannot run.",
+};
+
+pub const LLM_MODEL_MISSING_NAME: Rule = Rule {
+    code: "DL3102",
+    name: "llm-model-missing-name",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "LLM model has no name",
+    description: "An 'llm' node's 'model' has no 'name'. Select a model in the node settings.",
+};
+
+pub const LLM_MODEL_MISSING_PROVIDER: Rule = Rule {
+    code: "DL3103",
+    name: "llm-model-missing-provider",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "LLM model has no provider",
+    description: "An 'llm' node's 'model' has no 'provider', so Dify cannot resolve the \
+        model plugin.",
+};
+
+pub const LLM_EMPTY_PROMPT: Rule = Rule {
+    code: "DL3104",
+    name: "llm-empty-prompt",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "LLM node has an empty prompt",
+    description: "An 'llm' node's 'prompt_template' is missing or empty. The model will only \
+        see memory or context, which is rarely intended.",
+};
+
+pub const IF_ELSE_NO_CONDITIONS: Rule = Rule {
+    code: "DL3201",
+    name: "if-else-no-conditions",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "IF/ELSE node has no conditions",
+    description: "An 'if-else' node has neither 'cases' nor legacy 'conditions', so every run \
+        takes the else branch.",
+};
+
+pub const IF_ELSE_BRANCH_WITHOUT_EDGE: Rule = Rule {
+    code: "DL3202",
+    name: "if-else-branch-without-edge",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "IF/ELSE case or else branch has no outgoing edge",
+    description: "No edge leaves the IF/ELSE node with this case_id (or 'false' for the else \
+        branch) as its sourceHandle. Runs that take the branch end without output.",
+};
+
+pub const CLASSIFIER_NO_CLASSES: Rule = Rule {
+    code: "DL3301",
+    name: "classifier-no-classes",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Question Classifier has no classes",
+    description: "A 'question-classifier' node has no 'classes', so there is nothing to \
+        classify into.",
+};
+
+pub const CLASSIFIER_MISSING_MODEL: Rule = Rule {
+    code: "DL3302",
+    name: "classifier-missing-model",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Question Classifier has no model",
+    description: "A 'question-classifier' node has no 'model' configuration and cannot run.",
+};
+
+pub const CLASS_MISSING_ID: Rule = Rule {
+    code: "DL3303",
+    name: "class-missing-id",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Classifier class has no id",
+    description: "A class of a 'question-classifier' node has no 'id'. Outgoing edges use the \
+        class id as their sourceHandle, so the class cannot be connected.",
+};
+
+pub const CLASS_MISSING_NAME: Rule = Rule {
+    code: "DL3304",
+    name: "class-missing-name",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Classifier class has no name",
+    description: "A class of a 'question-classifier' node has no 'name', or an empty one. The \
+        name is what the model classifies against.",
+};
+
+pub const CLASS_WITHOUT_EDGE: Rule = Rule {
+    code: "DL3305",
+    name: "class-without-edge",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Classifier class has no outgoing edge",
+    description: "No edge leaves the classifier with this class id as its sourceHandle. \
+        Questions classified into it end the run without output.",
+};
+
+pub const CLASSIFIER_DUPLICATE_CLASS: Rule = Rule {
+    code: "DL3306",
+    name: "classifier-duplicate-class",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Classifier classes share an id or name",
+    description: "Two classes of a 'question-classifier' node have the same id, so edges \
+        cannot tell them apart, or the same name, so the model cannot.",
+};
+
+pub const CLASSIFIER_SIMILAR_CLASSES: Rule = Rule {
+    code: "DL3307",
+    name: "classifier-similar-classes",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Classifier class names are nearly identical",
+    description: "Two class names of a 'question-classifier' node differ only by a few \
+        characters, e.g. a typo or a plural. The model is likely to confuse them.",
+};
+
+pub const CLASS_NAME_UNKNOWN: Rule = Rule {
+    code: "DL3308",
+    name: "class-name-unknown",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Condition compares class_name with an unknown class",
+    description: "An IF/ELSE condition compares a classifier's 'class_name' output with a \
+        value that is not one of the classifier's class names, so it never matches.",
+};
+
+pub const AGGREGATOR_NO_VARIABLES: Rule = Rule {
+    code: "DL3401",
+    name: "aggregator-no-variables",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Variable Aggregator has no variables",
+    description: "A 'variable-aggregator' node has no input variables and always outputs \
+        nothing.",
+};
+
+pub const VARIABLE_ASSIGNER_UNSTABLE: Rule = Rule {
+    code: "DL3501",
+    name: "variable-assigner-unstable",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Legacy Variable Assigner node",
+    description: "The legacy 'variable-assigner' node can cause 'e.slice is not a function' \
+        errors in the Dify 1.10.x editor. Prefer a Variable Aggregator or the v2 'assigner' \
+        node.",
+};
+
+pub const VARIABLE_ASSIGNER_MISSING_WRITE_MODE: Rule = Rule {
+    code: "DL3502",
+    name: "variable-assigner-missing-write-mode",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Variable Assigner variable has no write_mode",
+    description: "Each variable of a legacy 'variable-assigner' node needs a 'write_mode' \
+        (e.g. 'over-write'); the editor crashes when it is missing.",
+};
+
+pub const ASSIGNER_VERSION: Rule = Rule {
+    code: "DL3503",
+    name: "assigner-version",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Assigner node is not version 2",
+    description: "'assigner' nodes with an 'items' list are the v2 format and should declare \
+        version: \"2\"; otherwise Dify may interpret them with the legacy schema.",
+};
+
+pub const ASSIGNER_MISSING_ITEMS: Rule = Rule {
+    code: "DL3504",
+    name: "assigner-missing-items",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Assigner node has no items",
+    description: "A v2 'assigner' node has no 'items' list describing what to assign.",
+};
+
+pub const ASSIGNER_ITEM_INCOMPLETE: Rule = Rule {
+    code: "DL3505",
+    name: "assigner-item-incomplete",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Assigner item is missing a required field",
+    description: "Each item of a v2 'assigner' node needs a 'variable_selector' naming the \
+        conversation variable to write and an 'operation' (overwrite, append, clear, ...).",
+};
+
+pub const ANSWER_EMPTY: Rule = Rule {
+    code: "DL3601",
+    name: "answer-empty",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Answer node has an empty answer",
+    description: "An 'answer' node's 'answer' text is missing or empty, so it streams nothing \
+        to the user.",
+};
+
+pub const CODE_MISSING_CODE: Rule = Rule {
+    code: "DL3701",
+    name: "code-missing-code",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Code node has no code",
+    description: "A 'code' node's 'code' field is missing or empty.",
+};
+
+pub const CODE_MISSING_LANGUAGE: Rule = Rule {
+    code: "DL3702",
+    name: "code-missing-language",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Code node has no code_language",
+    description: "A 'code' node has no 'code_language'. Dify needs it to pick the sandbox \
+        runtime.",
+};
+
+pub const CODE_UNKNOWN_LANGUAGE: Rule = Rule {
+    code: "DL3703",
+    name: "code-unknown-language",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Code node uses an unknown language",
+    description: "A 'code' node's 'code_language' is not one the sandbox supports \
+        (python3 or javascript, or code.languages in .difylint.toml).",
+};
+
+pub const CODE_MISSING_OUTPUTS: Rule = Rule {
+    code: "DL3704",
+    name: "code-missing-outputs",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Code node has no outputs",
+    description: "A 'code' node has no 'outputs' definition, so downstream nodes cannot \
+        reference its results.",
+};
+
+pub const TOOL_MISSING_PROVIDER: Rule = Rule {
+    code: "DL3801",
+    name: "tool-missing-provider",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Tool node has no provider_id",
+    description: "A 'tool' node has no 'provider_id', so Dify cannot find the tool plugin.",
+};
+
+pub const TOOL_MISSING_NAME: Rule = Rule {
+    code: "DL3802",
+    name: "tool-missing-name",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Tool node has no tool_name",
+    description: "A 'tool' node has no 'tool_name', so Dify cannot tell which tool of the \
+        provider to call.",
+};
+
+pub const TOOL_UNKNOWN_TOOL: Rule = Rule {
+    code: "DL3803",
+    name: "tool-unknown-tool",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Unknown tool for a known provider",
+    description: "The 'tool_name' is not one of the tools the provider is known to offer.",
+};
+
+pub const TOOL_UNKNOWN_PROVIDER: Rule = Rule {
+    code: "DL3804",
+    name: "tool-unknown-provider",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Unknown tool provider",
+    description: "The 'provider_id' is not a known tool provider. The plugin may not be \
+        installed on the target Dify instance. Known providers can be declared with \
+        tools.providers in .difylint.toml.",
+};
+
+pub const ITERATION_MISSING_ITERATOR: Rule = Rule {
+    code: "DL3901",
+    name: "iteration-missing-iterator",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Iteration node has no iterator_selector",
+    description: "An 'iteration' node has no 'iterator_selector' naming the array to iterate \
+        over.",
+};
+
+pub const ITERATION_MISSING_OUTPUT: Rule = Rule {
+    code: "DL3902",
+    name: "iteration-missing-output",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Iteration node has no output_selector",
+    description: "An 'iteration' node has no 'output_selector', so its 'output' array is \
+        always empty.",
+};
+
+pub const ITERATION_INVALID_START: Rule = Rule {
+    code: "DL3903",
+    name: "iteration-invalid-start",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Iteration start_node_id is not its iteration-start child",
+    description: "An iteration's 'start_node_id' must name the 'iteration-start' node inside \
+        that iteration. Otherwise Dify cannot enter the iteration body.",
+};
+
+pub const ITERATION_UNKNOWN_PARENT: Rule = Rule {
+    code: "DL3904",
+    name: "iteration-unknown-parent",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Node is inside an iteration that does not exist",
+    description: "The node's 'iteration_id' (or 'parentId') does not name an iteration node, \
+        or the two disagree. The node is left floating outside any container on import.",
+};
+
+pub const ITERATION_OUTPUT_OUTSIDE: Rule = Rule {
+    code: "DL3905",
+    name: "iteration-output-outside",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Iteration output_selector points outside the iteration",
+    description: "An iteration collects its output from a node in its own body. An \
+        'output_selector' naming a node outside the iteration yields an empty array.",
+};
+
+pub const LOOP_INVALID_COUNT: Rule = Rule {
+    code: "DL3921",
+    name: "loop-invalid-count",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Loop count is missing or out of range",
+    description: "A 'loop' node's 'loop_count' must be a whole number between 1 and the \
+        instance's maximum (LOOP_NODE_MAX_COUNT, 100 by default). The limit can be changed \
+        with loops.max_count in .difylint.toml.",
+};
+
+pub const LOOP_INVALID_BREAK_CONDITION: Rule = Rule {
+    code: "DL3922",
+    name: "loop-invalid-break-condition",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Loop break condition is incomplete or invalid",
+    description: "Each entry in 'break_conditions' needs a 'variable_selector' and a known \
+        'comparison_operator'.",
+};
+
+pub const LOOP_INVALID_VARIABLE: Rule = Rule {
+    code: "DL3923",
+    name: "loop-invalid-variable",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Loop variable is incomplete or its initial value has the wrong type",
+    description: "Each loop variable needs a unique 'label', a known 'var_type' and a \
+        'value_type' of 'constant' or 'variable'. A constant initial value must match the \
+        variable type; a variable initial value must be a selector.",
+};
+
+pub const LOOP_INVALID_START: Rule = Rule {
+    code: "DL3924",
+    name: "loop-invalid-start",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Loop start_node_id is not its loop-start child",
+    description: "A loop's 'start_node_id' must name the 'loop-start' node inside that loop. \
+        Otherwise Dify cannot enter the loop body.",
+};
+
+pub const LOOP_UNKNOWN_PARENT: Rule = Rule {
+    code: "DL3925",
+    name: "loop-unknown-parent",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Node is inside a loop that does not exist",
+    description: "The node's 'loop_id' (or 'parentId') does not name a loop node, or the two \
+        disagree. 'loop-end' nodes must be inside a loop.",
+};
+
+pub const EXTRACTOR_MISSING_MODEL: Rule = Rule {
+    code: "DL3931",
+    name: "extractor-missing-model",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Parameter extractor has no model",
+    description: "A 'parameter-extractor' node has no 'model', or its model has no name or \
+        provider. The node needs a model to extract parameters.",
+};
+
+pub const EXTRACTOR_MISSING_QUERY: Rule = Rule {
+    code: "DL3932",
+    name: "extractor-missing-query",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Parameter extractor has no query input",
+    description: "A 'parameter-extractor' node's 'query' selector is missing or empty, so \
+        there is no text to extract parameters from.",
+};
+
+pub const EXTRACTOR_INVALID_PARAMETER: Rule = Rule {
+    code: "DL3933",
+    name: "extractor-invalid-parameter",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Parameter extractor parameter is malformed",
+    description: "A 'parameter-extractor' node has no parameters, or a parameter has no name, \
+        a duplicate or invalid name, an unknown type, a 'select' type without options, or a \
+        non-boolean 'required'. Parameter names become the node's output variables.",
+};
+
+pub const EXTRACTOR_INVALID_REASONING_MODE: Rule = Rule {
+    code: "DL3934",
+    name: "extractor-invalid-reasoning-mode",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Unknown parameter extractor reasoning mode",
+    description: "A 'parameter-extractor' node's 'reasoning_mode' is not 'function_call' or \
+        'prompt'.",
+};
+
+pub const KNOWLEDGE_MISSING_DATASETS: Rule = Rule {
+    code: "DL3951",
+    name: "knowledge-missing-datasets",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Knowledge Retrieval node has no datasets",
+    description: "A 'knowledge-retrieval' node has no 'dataset_ids'. Knowledge bases are not \
+        part of the export and must be re-linked after import.",
+};
+
+pub const KNOWLEDGE_MISSING_RETRIEVAL_MODE: Rule = Rule {
+    code: "DL3952",
+    name: "knowledge-missing-retrieval-mode",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Knowledge Retrieval node has no retrieval_mode",
+    description: "A 'knowledge-retrieval' node has no 'retrieval_mode' (single or multiple).",
+};
+
+pub const KNOWLEDGE_UNKNOWN_RETRIEVAL_MODE: Rule = Rule {
+    code: "DL3953",
+    name: "knowledge-unknown-retrieval-mode",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Unknown retrieval_mode",
+    description: "A 'knowledge-retrieval' node's 'retrieval_mode' is neither 'single' nor \
+        'multiple'.",
+};
+
+pub const KNOWLEDGE_OPENAI_RERANK: Rule = Rule {
+    code: "DL3954",
+    name: "knowledge-openai-rerank",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Rerank model uses OpenAI",
+    description: "The reranking model is provided by OpenAI, which fails on instances without \
+        the OpenAI plugin. Consider weighted_score mode instead.",
+};
+
+pub const KNOWLEDGE_UNKNOWN_DATASET: Rule = Rule {
+    code: "DL3955",
+    name: "knowledge-unknown-dataset",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Knowledge base not in the configured dataset IDs",
+    description: "A 'knowledge-retrieval' node uses a dataset ID that is not listed in \
+        knowledge.dataset_ids of .difylint.toml. Only checked when that list is configured.",
+};
+
+pub const TEMPLATE_INVALID: Rule = Rule {
+    code: "DL3961",
+    name: "template-invalid",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Template is missing or is not valid Jinja2",
+    description: "A 'template-transform' node's 'template' does not parse as Jinja2, e.g. \
+        an unclosed '{% for %}' block or an unbalanced '{{'. The node fails at run time.",
+};
+
+pub const TEMPLATE_UNKNOWN_FILTER: Rule = Rule {
+    code: "DL3962",
+    name: "template-unknown-filter",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Template uses a filter Jinja2 does not provide",
+    description: "The template applies a filter that is not a Jinja2 built-in. Dify renders \
+        templates without custom filters, so the node fails at run time.",
+};
+
+pub const TEMPLATE_UNDECLARED_VARIABLE: Rule = Rule {
+    code: "DL3963",
+    name: "template-undeclared-variable",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Template uses a variable missing from the node's variables",
+    description: "Only the variables declared in the node's 'variables' list are passed to \
+        the template. Any other name renders as an empty string.",
+};
+
+pub const TEMPLATE_UNUSED_VARIABLE: Rule = Rule {
+    code: "DL3964",
+    name: "template-unused-variable",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Declared template variable is never used",
+    description: "The variable is declared in the node's 'variables' list but the template \
+        never reads it. It may be left over from an edit, or the template may have a typo.",
+};
+
+pub const TEMPLATE_VARIABLE_INVALID: Rule = Rule {
+    code: "DL3965",
+    name: "template-variable-invalid",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Template variable has no name or selector",
+    description: "Each entry in a 'template-transform' node's 'variables' needs a unique \
+        'variable' name and a 'value_selector' naming the value to pass in.",
+};
+
+pub const HTTP_INVALID_METHOD: Rule = Rule {
+    code: "DL3971",
+    name: "http-invalid-method",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "HTTP request method is missing or unknown",
+    description: "An 'http-request' node's 'method' must be one of get, post, put, patch, \
+        delete, head or options.",
+};
+
+pub const HTTP_INVALID_URL: Rule = Rule {
+    code: "DL3972",
+    name: "http-invalid-url",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "HTTP request URL is missing or does not parse",
+    description: "The 'url' must be an absolute http:// or https:// URL once its variable \
+        references are filled in. Dify rejects anything else when the node runs.",
+};
+
+pub const HTTP_INVALID_BODY: Rule = Rule {
+    code: "DL3973",
+    name: "http-invalid-body",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "HTTP request body does not match its type",
+    description: "The body content does not fit the body 'type': a JSON body that does not \
+        parse, a form field without a key, a file field without a file selector, or a \
+        binary body without a file.",
+};
+
+pub const HTTP_INCOMPLETE_AUTHORIZATION: Rule = Rule {
+    code: "DL3974",
+    name: "http-incomplete-authorization",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "HTTP request API key authorization is incomplete",
+    description: "An 'api-key' authorization needs a 'config' with a type of basic, bearer or \
+        custom and a non-empty 'api_key'. Custom authorization also needs a 'header'.",
+};
+
+pub const HTTP_INVALID_TIMEOUT: Rule = Rule {
+    code: "DL3975",
+    name: "http-invalid-timeout",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "HTTP request timeout is not positive or exceeds the maximum",
+    description: "Connect, read and write timeouts must be positive and no larger than the \
+        node's max_*_timeout (Dify's defaults are 300, 600 and 600 seconds).",
+};
+
+pub const HTTP_INVALID_RETRY: Rule = Rule {
+    code: "DL3976",
+    name: "http-invalid-retry",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "HTTP request retry settings are out of range",
+    description: "With retries enabled, 'max_retries' must be between 1 and 10 and \
+        'retry_interval' between 100 and 5000 milliseconds, the range the Dify editor allows.",
+};
+
+pub const START_VARIABLE_INVALID: Rule = Rule {
+    code: "DL3981",
+    name: "start-variable-invalid",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Start variable is malformed",
+    description: "A start node input variable has no name, a duplicate or invalid name, an \
+        unknown type, a non-boolean 'required', a 'max_length' that is not a positive integer, \
+        or a default longer than its 'max_length'.",
+};
+
+pub const START_SELECT_INVALID: Rule = Rule {
+    code: "DL3982",
+    name: "start-select-invalid",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "Select input has no options or an invalid default",
+    description: "A 'select' start variable has no options, or its default is not one of them. \
+        Users cannot pick a value, or the form starts with a value it cannot show.",
+};
+
+pub const START_VARIABLE_MISSING_LABEL: Rule = Rule {
+    code: "DL3983",
+    name: "start-variable-missing-label",
+    category: Category::Node,
+    default_severity: Severity::Warning,
+    summary: "Start variable has no label",
+    description: "A start node input variable has no 'label', so the run form shows an \
+        unnamed field.",
+};
+
+pub const END_OUTPUT_INVALID: Rule = Rule {
+    code: "DL3991",
+    name: "end-output-invalid",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "End output is malformed",
+    description: "An End node output has no 'variable' name, shares its name with another \
+        output, or has no 'value_selector'.",
+};
+
+pub const END_OUTPUT_UNAVAILABLE: Rule = Rule {
+    code: "DL3992",
+    name: "end-output-unavailable",
+    category: Category::Node,
+    default_severity: Severity::Error,
+    summary: "End output reads a node that may not have run",
+    description: "An End node output selects the output of a node that does not run on every \
+        path into the End node, e.g. one side of an IF/ELSE or a parallel branch. The output \
+        is empty whenever the End node is reached another way.",
+};
+
+// DL4xxx: variables and references
+
+pub const REFERENCE_NODE_MISSING: Rule = Rule {
+    code: "DL4001",
+    name: "reference-node-missing",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to a non-existent node",
+    description: "A variable selector or {{#node.var#}} template reference names a node ID \
+        that is not in the graph. Dify resolves it to an empty value or fails at runtime.",
+};
+
+pub const START_VARIABLES_EMPTY: Rule = Rule {
+    code: "DL4002",
+    name: "start-variables-empty",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to a start variable, but start has none",
+    description: "A selector reads a variable from the start node, but the start node declares \
+        no input variables. Use sys.query for the chat input or add the variable to the start \
+        node.",
+};
+
+pub const REFERENCE_OUTPUT_MISSING: Rule = Rule {
+    code: "DL4003",
+    name: "reference-output-missing",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to an output variable the node does not produce",
+    description: "A selector or {{#node.var#}} reference names an output the referenced node \
+        does not have, e.g. '{{#llm.txt#}}' instead of '{{#llm.text#}}'. Outputs are known \
+        per node type: declared outputs for Code, parameters for Parameter Extractor, \
+        variables for Start, and fixed names such as 'text', 'body' or 'result' otherwise. \
+        Dify resolves the reference to an empty value at run time.",
+};
+
+pub const ITERATION_ITEM_OUTSIDE: Rule = Rule {
+    code: "DL4004",
+    name: "iteration-item-outside",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Iteration item or index used outside the iteration",
+    description: "'item' and 'index' of an iteration only exist while its body runs. A \
+        reference to them from a node outside the iteration is always empty; use the \
+        iteration's 'output' instead.",
+};
+
+pub const LOOP_VARIABLE_OUTSIDE: Rule = Rule {
+    code: "DL4005",
+    name: "loop-variable-outside",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Loop variable used outside the loop",
+    description: "Loop variables only exist while the loop body runs. A reference to one \
+        from a node outside the loop is always empty.",
+};
+
+pub const SYSTEM_VARIABLE_UNKNOWN: Rule = Rule {
+    code: "DL4006",
+    name: "system-variable-unknown",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to an unknown system variable",
+    description: "A node references `sys.NAME` but Dify provides no system variable with \
+        that name.",
+};
+
+pub const CHAT_ONLY_VARIABLE: Rule = Rule {
+    code: "DL4007",
+    name: "chat-only-variable",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Chat-only variable used in a workflow app",
+    description: "`sys.query`, `sys.conversation_id`, `sys.dialogue_count` and conversation \
+        variables only exist in advanced-chat apps. In a workflow app they are always empty.",
+};
+
+pub const REFERENCE_NOT_RUN: Rule = Rule {
+    code: "DL4008",
+    name: "reference-not-run",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to a node that has not run yet",
+    description: "A selector or {{#node.var#}} reference reads a node that never runs before \
+        the referencing node: it is downstream, on a parallel branch, or on another branch of \
+        an IF/ELSE. Dify resolves it to an empty value or fails at runtime.",
+};
+
+pub const REFERENCE_SOME_PATHS: Rule = Rule {
+    code: "DL4009",
+    name: "reference-some-paths",
+    category: Category::Variable,
+    default_severity: Severity::Warning,
+    summary: "Reference to a node that only runs on some paths",
+    description: "A reference reads a node that runs before the referencing node on some \
+        paths but not all, e.g. one side of an IF/ELSE. The value is empty whenever another \
+        path is taken; merge the branches with a Variable Aggregator first.",
+};
+
+pub const CONVERSATION_VARIABLE_MISSING_TYPE: Rule = Rule {
+    code: "DL4101",
+    name: "conversation-variable-missing-type",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Conversation variable has no value_type",
+    description: "A conversation variable has no 'value_type'; Dify rejects it on import.",
+};
+
+pub const CONVERSATION_VARIABLE_MISSING_VALUE: Rule = Rule {
+    code: "DL4102",
+    name: "conversation-variable-missing-value",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Conversation variable has no value",
+    description: "A conversation variable has no default 'value'; Dify rejects it on import.",
+};
+
+pub const CONVERSATION_VARIABLE_UNDEFINED: Rule = Rule {
+    code: "DL4103",
+    name: "conversation-variable-undefined",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to an undeclared conversation variable",
+    description: "A node references `conversation.NAME` but the workflow declares no \
+        conversation variable with that name.",
+};
+
+pub const ENVIRONMENT_VARIABLE_INVALID: Rule = Rule {
+    code: "DL4201",
+    name: "environment-variable-invalid",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Environment variable is malformed",
+    description: "An environment variable has no name, shares its name with another, has a \
+        value_type other than string, number or secret, or a number variable holds \
+        a non-numeric value.",
+};
+
+pub const ENVIRONMENT_VARIABLE_UNDEFINED: Rule = Rule {
+    code: "DL4202",
+    name: "environment-variable-undefined",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Reference to an undeclared environment variable",
+    description: "A node references `env.NAME` but the workflow declares no environment \
+        variable with that name.",
+};
+
+pub const ENVIRONMENT_VARIABLE_UNUSED: Rule = Rule {
+    code: "DL4203",
+    name: "environment-variable-unused",
+    category: Category::Variable,
+    default_severity: Severity::Warning,
+    summary: "Environment variable is never referenced",
+    description: "An environment variable is declared but no node references it.",
+};
+
+pub const ITERATION_INPUT_NOT_ARRAY: Rule = Rule {
+    code: "DL4301",
+    name: "iteration-input-not-array",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Iteration input is not an array",
+    description: "An iteration's 'iterator_selector' points at a variable whose type is not an \
+        array. Dify fails the run when the iteration starts.",
+};
+
+pub const OPERATOR_TYPE_MISMATCH: Rule = Rule {
+    code: "DL4302",
+    name: "operator-type-mismatch",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Comparison operator does not fit the variable type",
+    description: "An IF/ELSE condition uses an operator Dify does not offer for the variable's \
+        type, e.g. 'contains' on a number or '>' on a string. The condition fails at run time.",
+};
+
+pub const AGGREGATOR_TYPE_MISMATCH: Rule = Rule {
+    code: "DL4303",
+    name: "aggregator-type-mismatch",
+    category: Category::Variable,
+    default_severity: Severity::Error,
+    summary: "Aggregated variable does not match output_type",
+    description: "A variable aggregator input has a different type from the aggregator's (or \
+        group's) 'output_type'. All inputs must share the declared type.",
+};
+
+// DL5xxx: models
+
+pub const UNKNOWN_MODEL: Rule = Rule {
+    code: "DL5001",
+    name: "unknown-model",
+    category: Category::Model,
+    default_severity: Severity::Warning,
+    summary: "Unknown model name",
+    description: "The model name of an LLM or classifier node is not in the list of known \
+        models, so it may not be available on the target instance. The list can be replaced \
+        with models.allowed in .difylint.toml.",
+};
+
+pub const UNKNOWN_MODEL_PROVIDER: Rule = Rule {
+    code: "DL5002",
+    name: "unknown-model-provider",
+    category: Category::Model,
+    default_severity: Severity::Warning,
+    summary: "Model provider not allowed",
+    description: "The model provider of an LLM or classifier node is not listed in \
+        models.providers of .difylint.toml. Only checked when that list is configured.",
+};
+
+// DL6xxx: chat, agent and completion apps
+
+pub const APP_MISSING_MODEL: Rule = Rule {
+    code: "DL6001",
+    name: "app-missing-model",
+    category: Category::App,
+    default_severity: Severity::Error,
+    summary: "App model_config has no model name or provider",
+    description: "model_config.model must name the model and its provider. Dify cannot run \
+        the app until a model is selected.",
+};
+
+pub const APP_EMPTY_PROMPT: Rule = Rule {
+    code: "DL6002",
+    name: "app-empty-prompt",
+    category: Category::App,
+    default_severity: Severity::Warning,
+    summary: "App prompt is empty",
+    description: "The app's pre_prompt (or, with prompt_type: advanced, every chat or \
+        completion prompt message) is empty, so the model only sees the user's input.",
+};
+
+pub const APP_PROMPT_UNDEFINED_VARIABLE: Rule = Rule {
+    code: "DL6003",
+    name: "app-prompt-undefined-variable",
+    category: Category::App,
+    default_severity: Severity::Error,
+    summary: "Prompt uses a variable that is not an input field",
+    description: "The prompt contains {{name}} but user_input_form has no field with that \
+        variable. Dify leaves the placeholder empty. Built-in placeholders such as \
+        {{#context#}}, {{#histories#}} and {{#query#}} are allowed.",
+};
+
+pub const APP_INPUT_FIELD_INVALID: Rule = Rule {
+    code: "DL6004",
+    name: "app-input-field-invalid",
+    category: Category::App,
+    default_severity: Severity::Error,
+    summary: "Invalid user_input_form field",
+    description: "An input field has no variable name or label, reuses another field's \
+        variable, or is a select field without options.",
+};
+
+pub const AGENT_TOOL_INCOMPLETE: Rule = Rule {
+    code: "DL6005",
+    name: "agent-tool-incomplete",
+    category: Category::App,
+    default_severity: Severity::Error,
+    summary: "Agent tool is missing provider_id or tool_name",
+    description: "Every entry in agent_mode.tools needs the provider_id and tool_name of the \
+        tool the agent may call.",
+};
+
+pub const AGENT_MODE_DISABLED: Rule = Rule {
+    code: "DL6006",
+    name: "agent-mode-disabled",
+    category: Category::App,
+    default_severity: Severity::Warning,
+    summary: "Agent app without agent_mode enabled",
+    description: "An agent-chat app has agent_mode missing or disabled, so it runs as a plain \
+        chat app and never calls its tools.",
+};
+
+pub const FILE_UPLOAD_NO_TYPES: Rule = Rule {
+    code: "DL6007",
+    name: "file-upload-no-types",
+    category: Category::App,
+    default_severity: Severity::Warning,
+    summary: "File upload enabled without allowed file types",
+    description: "file_upload is enabled but neither allowed_file_types nor image upload is \
+        set, so users cannot attach any file.",
+};
+
+// DL7xxx: secrets
+
+pub const HARDCODED_SECRET: Rule = Rule {
+    code: "DL7001",
+    name: "hardcoded-secret",
+    category: Category::Security,
+    default_severity: Severity::Error,
+    summary: "Credential written into the DSL",
+    description: "A value matches a known credential format (OpenAI, Anthropic, AWS, Tavily, \
+        GitHub, Google, Slack or a bearer token), or a secret environment variable was \
+        exported with its value. Exports are committed to git, so the credential leaks. \
+        Store it in a 'secret' environment variable and reference it as {{#env.NAME#}}.",
+};
+
+pub const POSSIBLE_SECRET: Rule = Rule {
+    code: "DL7002",
+    name: "possible-secret",
+    category: Category::Security,
+    default_severity: Severity::Warning,
+    summary: "Field that looks like a credential holds a high-entropy literal",
+    description: "A key, token, password or secret field (or such an assignment in code) \
+        holds a random-looking literal instead of a variable reference. If it is a \
+        credential, move it to a 'secret' environment variable.",
+};
+
+/// Every rule, in code order
+pub static RULES: &[&Rule] = &[
+    &PARSE_ERROR,
+    &EDGE_TARGET_MISSING,
+    &EDGE_SOURCE_MISSING,
+    &ORPHAN_NODE,
+    &UNREACHABLE_NODE,
+    &DEAD_END_NODE,
+    &GRAPH_CYCLE,
+    &EDGE_INVALID_SOURCE_HANDLE,
+    &EDGE_CROSSES_CONTAINER,
+    &PARALLEL_END_NODES,
+    &MISSING_APP,
+    &MISSING_WORKFLOW,
+    &MISSING_GRAPH,
+    &NO_NODES,
+    &MISSING_START_NODE,
+    &MULTIPLE_START_NODES,
+    &MISSING_MODEL_CONFIG,
+    &UNKNOWN_APP_MODE,
+    &MISSING_OUTPUT_NODE,
+    &WRONG_OUTPUT_NODE,
+    &NODE_MISSING_DATA,
+    &NODE_MISSING_ID,
+    &NODE_MISSING_TYPE,
+    &LLM_MISSING_MODEL,
+    &LLM_MODEL_MISSING_NAME,
+    &LLM_MODEL_MISSING_PROVIDER,
+    &LLM_EMPTY_PROMPT,
+    &IF_ELSE_NO_CONDITIONS,
+    &IF_ELSE_BRANCH_WITHOUT_EDGE,
+    &CLASSIFIER_NO_CLASSES,
+    &CLASSIFIER_MISSING_MODEL,
+    &CLASS_MISSING_ID,
+    &CLASS_MISSING_NAME,
+    &CLASS_WITHOUT_EDGE,
+    &CLASSIFIER_DUPLICATE_CLASS,
+    &CLASSIFIER_SIMILAR_CLASSES,
+    &CLASS_NAME_UNKNOWN,
+    &AGGREGATOR_NO_VARIABLES,
+    &VARIABLE_ASSIGNER_UNSTABLE,
+    &VARIABLE_ASSIGNER_MISSING_WRITE_MODE,
+    &ASSIGNER_VERSION,
+    &ASSIGNER_MISSING_ITEMS,
+    &ASSIGNER_ITEM_INCOMPLETE,
+    &ANSWER_EMPTY,
+    &CODE_MISSING_CODE,
+    &CODE_MISSING_LANGUAGE,
+    &CODE_UNKNOWN_LANGUAGE,
+    &CODE_MISSING_OUTPUTS,
+    &TOOL_MISSING_PROVIDER,
+    &TOOL_MISSING_NAME,
+    &TOOL_UNKNOWN_TOOL,
+    &TOOL_UNKNOWN_PROVIDER,
+    &ITERATION_MISSING_ITERATOR,
+    &ITERATION_MISSING_OUTPUT,
+    &ITERATION_INVALID_START,
+    &ITERATION_UNKNOWN_PARENT,
+    &ITERATION_OUTPUT_OUTSIDE,
+    &LOOP_INVALID_COUNT,
+    &LOOP_INVALID_BREAK_CONDITION,
+    &LOOP_INVALID_VARIABLE,
+    &LOOP_INVALID_START,
+    &LOOP_UNKNOWN_PARENT,
+    &EXTRACTOR_MISSING_MODEL,
+    &EXTRACTOR_MISSING_QUERY,
+    &EXTRACTOR_INVALID_PARAMETER,
+    &EXTRACTOR_INVALID_REASONING_MODE,
+    &KNOWLEDGE_MISSING_DATASETS,
+    &KNOWLEDGE_MISSING_RETRIEVAL_MODE,
+    &KNOWLEDGE_UNKNOWN_RETRIEVAL_MODE,
+    &KNOWLEDGE_OPENAI_RERANK,
+    &KNOWLEDGE_UNKNOWN_DATASET,
+    &TEMPLATE_INVALID,
+    &TEMPLATE_UNKNOWN_FILTER,
+    &TEMPLATE_UNDECLARED_VARIABLE,
+    &TEMPLATE_UNUSED_VARIABLE,
+    &TEMPLATE_VARIABLE_INVALID,
+    &HTTP_INVALID_METHOD,
+    &HTTP_INVALID_URL,
+    &HTTP_INVALID_BODY,
+    &HTTP_INCOMPLETE_AUTHORIZATION,
+    &HTTP_INVALID_TIMEOUT,
+    &HTTP_INVALID_RETRY,
+    &START_VARIABLE_INVALID,
+    &START_SELECT_INVALID,
+    &START_VARIABLE_MISSING_LABEL,
+    &END_OUTPUT_INVALID,
+    &END_OUTPUT_UNAVAILABLE,
+    &REFERENCE_NODE_MISSING,
+    &START_VARIABLES_EMPTY,
+    &REFERENCE_OUTPUT_MISSING,
+    &ITERATION_ITEM_OUTSIDE,
+    &LOOP_VARIABLE_OUTSIDE,
+    &SYSTEM_VARIABLE_UNKNOWN,
+    &CHAT_ONLY_VARIABLE,
+    &REFERENCE_NOT_RUN,
+    &REFERENCE_SOME_PATHS,
+    &CONVERSATION_VARIABLE_MISSING_TYPE,
+    &CONVERSATION_VARIABLE_MISSING_VALUE,
+    &CONVERSATION_VARIABLE_UNDEFINED,
+    &ENVIRONMENT_VARIABLE_INVALID,
+    &ENVIRONMENT_VARIABLE_UNDEFINED,
+    &ENVIRONMENT_VARIABLE_UNUSED,
+    &ITERATION_INPUT_NOT_ARRAY,
+    &OPERATOR_TYPE_MISMATCH,
+    &AGGREGATOR_TYPE_MISMATCH,
+    &UNKNOWN_MODEL,
+    &UNKNOWN_MODEL_PROVIDER,
+    &APP_MISSING_MODEL,
+    &APP_EMPTY_PROMPT,
+    &APP_PROMPT_UNDEFINED_VARIABLE,
+    &APP_INPUT_FIELD_INVALID,
+    &AGENT_TOOL_INCOMPLETE,
+    &AGENT_MODE_DISABLED,
+    &FILE_UPLOAD_NO_TYPES,
+    &HARDCODED_SECRET,
+    &POSSIBLE_SECRET,
+];
+
+/// Look up a rule by code (`DL1001`) or name (`edge-target-missing`)
+pub fn find_rule(code_or_name: &str) -> Option<&'static Rule> {
+    RULES
+        .iter()
+        .copied()
+        .find(|r| r.code.eq_ignore_ascii_case(code_or_name) || r.name == code_or_name)
+}
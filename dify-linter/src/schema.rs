@@ -0,0 +1,118 @@
+use crate::types::Node;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Names Dify accepts for input variables and extracted parameters
+pub static IDENTIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+/// Output variables a node exposes to downstream nodes, or None if they cannot be
+/// known from the DSL (e.g. tools with provider-specific outputs)
+pub fn node_outputs(node: &Node) -> Option<Vec<String>> {
+    let data = node.data.as_ref()?;
+    let fixed = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+    let outputs = match node.node_type() {
+        "start" => named_items(data.variables.as_ref(), "variable"),
+        "llm" => fixed(&[
+            "text",
+            "reasoning_content",
+            "usage",
+            "finish_reason",
+            "structured_output",
+            "files",
+        ]),
+        "agent" => fixed(&["text", "usage", "files", "json"]),
+        "code" => match data.extra.get("outputs") {
+            Some(Value::Object(outputs)) => outputs.keys().cloned().collect(),
+            _ => Vec::new(),
+        },
+        "http-request" => fixed(&["body", "status_code", "headers", "files"]),
+        "knowledge-retrieval" => fixed(&["result"]),
+        "document-extractor" => fixed(&["text"]),
+        "list-operator" => fixed(&["result", "first_record", "last_record"]),
+        "template-transform" => fixed(&["output"]),
+        "question-classifier" => fixed(&["class_name", "class_id", "usage"]),
+        "parameter-extractor" => {
+            let mut outputs = named_items(data.extra.get("parameters"), "name");
+            outputs.extend(fixed(&["__is_success", "__reason", "__usage"]));
+            outputs
+        }
+        // Aggregators expose 'output', or one object per group when grouping is enabled
+        "variable-aggregator" | "variable-assigner" => {
+            let settings = data.extra.get("advanced_settings");
+            let grouped = settings
+                .and_then(|s| s.get("group_enabled"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if grouped {
+                named_items(settings.and_then(|s| s.get("groups")), "group_name")
+            } else {
+                fixed(&["output"])
+            }
+        }
+        // 'item' and 'index' are visible to the nodes inside the iteration
+        "iteration" => fixed(&["output", "item", "index"]),
+        // Loop variables are visible to the nodes inside the loop
+        "loop" => named_items(data.extra.get("loop_variables"), "label"),
+        "answer" => fixed(&["answer", "files"]),
+        "end" | "if-else" | "assigner" | "iteration-start" | "loop-start" | "loop-end" => {
+            Vec::new()
+        }
+        _ => return None,
+    };
+
+    // Duplicate declarations are reported by the node checks
+    let mut seen = HashSet::new();
+    Some(
+        outputs
+            .into_iter()
+            .filter(|o| seen.insert(o.clone()))
+            .collect(),
+    )
+}
+
+/// System variables available to every workflow and advanced-chat app
+pub const SYSTEM_VARIABLES: &[&str] = &[
+    "files",
+    "user_id",
+    "app_id",
+    "workflow_id",
+    "workflow_run_id",
+];
+
+/// System variables that only exist in advanced-chat apps
+pub const CHAT_SYSTEM_VARIABLES: &[&str] = &["query", "conversation_id", "dialogue_count"];
+
+/// Whether `name` (the second selector segment) is an output of `node`
+pub fn has_output(node: &Node, outputs: &[String], name: &str) -> bool {
+    // Older exports read system variables through the start node, e.g. [start, sys.query]
+    if node.node_type() == "start" && (name == "sys" || name.starts_with("sys.")) {
+        return true;
+    }
+    outputs.iter().any(|o| o == name)
+}
+
+/// Closest candidate to a misspelt name, if any is close enough to suggest
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    // Stay below the name's length, or every short name would match every other
+    let len = name.chars().count();
+    let max_distance = (len / 3).max(1).min(len.saturating_sub(1));
+    candidates
+        .into_iter()
+        .map(|c| (strsim::damerau_levenshtein(name, c), c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Values of `field` for each object in a list, e.g. start variable names
+fn named_items(list: Option<&Value>, field: &str) -> Vec<String> {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.get(field)?.as_str().map(str::to_string))
+        .collect()
+}
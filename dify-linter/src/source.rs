@@ -0,0 +1,431 @@
+use saphyr_parser::{Event, Parser, Span as YamlSpan, SpannedEventReceiver};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Byte range of a YAML value (including its key for mapping entries)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Position in the original file, as shown to the user
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Loaded file content with the YAML extracted from any JSON wrapper
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// Original file content
+    pub text: String,
+    /// YAML document (equal to `text` unless JSON-wrapped)
+    pub yaml: String,
+    /// Byte range of the `data` string literal in `text`, quotes included
+    pub data_range: Option<Range<usize>>,
+    /// Original byte offset for every YAML byte (plus one past the end)
+    offsets: Option<Vec<usize>>,
+}
+
+impl SourceFile {
+    /// Plain YAML file
+    pub fn from_yaml(text: String) -> Self {
+        Self {
+            yaml: text.clone(),
+            text,
+            data_range: None,
+            offsets: None,
+        }
+    }
+
+    /// JSON export with the YAML in a top-level `data` string
+    pub fn from_json(text: String) -> Option<Self> {
+        let data_range = find_data_string(&text)?;
+        let (yaml, offsets) = decode_json_string(&text, data_range.clone())?;
+
+        Some(Self {
+            text,
+            yaml,
+            data_range: Some(data_range),
+            offsets: Some(offsets),
+        })
+    }
+
+    pub fn is_json_wrapped(&self) -> bool {
+        self.data_range.is_some()
+    }
+
+    /// Map a YAML byte offset to a byte offset in the original file
+    pub fn original_offset(&self, yaml_offset: usize) -> usize {
+        match &self.offsets {
+            Some(offsets) => offsets[yaml_offset.min(offsets.len() - 1)],
+            None => yaml_offset.min(self.text.len()),
+        }
+    }
+
+    /// Resolve a YAML span to a location in the original file
+    pub fn location(&self, span: Span) -> Location {
+        let start = self.original_offset(span.start);
+        let end = self.original_offset(span.end).max(start);
+        let (line, column) = line_column(&self.text, start);
+
+        Location {
+            line,
+            column,
+            start,
+            end,
+        }
+    }
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..floor_char_boundary(text, offset)];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut i = offset.min(text.len());
+    while !text.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+/// Find the string literal of the top-level `data` key in a JSON object
+fn find_data_string(json: &str) -> Option<Range<usize>> {
+    let bytes = json.as_bytes();
+    let mut depth = 0;
+    let mut expect_key = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i)?;
+                if depth == 1 && expect_key {
+                    expect_key = false;
+                    let mut j = skip_whitespace(bytes, end);
+                    if &json[i + 1..end - 1] == "data" && bytes.get(j) == Some(&b':') {
+                        j = skip_whitespace(bytes, j + 1);
+                        if bytes.get(j) == Some(&b'"') {
+                            return Some(j..string_end(bytes, j)?);
+                        }
+                    }
+                }
+                i = end;
+                continue;
+            }
+            b'{' => {
+                depth += 1;
+                expect_key = depth == 1;
+            }
+            b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b',' if depth == 1 => expect_key = true,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Index just past the closing quote of the string starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Decode a JSON string literal, recording the source offset of every output byte
+fn decode_json_string(json: &str, literal: Range<usize>) -> Option<(String, Vec<usize>)> {
+    let bytes = json.as_bytes();
+    let mut out = String::new();
+    let mut offsets = Vec::new();
+    let mut i = literal.start + 1;
+    let end = literal.end - 1;
+
+    while i < end {
+        let start = i;
+        let ch = if bytes[i] == b'\\' {
+            let esc = *bytes.get(i + 1)?;
+            i += 2;
+            match esc {
+                b'"' => '"',
+                b'\\' => '\\',
+                b'/' => '/',
+                b'b' => '\u{8}',
+                b'f' => '\u{c}',
+                b'n' => '\n',
+                b'r' => '\r',
+                b't' => '\t',
+                b'u' => {
+                    let hi = parse_hex4(json, i)?;
+                    i += 4;
+                    if (0xD800..0xDC00).contains(&hi) && json[i..].starts_with("\\u") {
+                        let lo = parse_hex4(json, i + 2)?;
+                        i += 6;
+                        char::from_u32(0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00))?
+                    } else {
+                        char::from_u32(hi)?
+                    }
+                }
+                _ => return None,
+            }
+        } else {
+            let ch = json[i..].chars().next()?;
+            i += ch.len_utf8();
+            ch
+        };
+
+        out.push(ch);
+        offsets.extend(std::iter::repeat_n(start, ch.len_utf8()));
+    }
+
+    offsets.push(end);
+    Some((out, offsets))
+}
+
+fn parse_hex4(json: &str, at: usize) -> Option<u32> {
+    u32::from_str_radix(json.get(at..at + 4)?, 16).ok()
+}
+
+/// Spans of every value in a YAML document, keyed by JSON pointer
+/// (e.g. `/workflow/graph/nodes/3/data/model`)
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    span: Span,
+    /// Start of the value itself, after any mapping key
+    value_start: usize,
+    mapping: bool,
+}
+
+impl SourceMap {
+    /// Build the map from YAML text; returns an empty map if the YAML does not parse
+    pub fn parse(yaml: &str) -> Self {
+        let mut collector = SpanCollector::new(yaml);
+        match Parser::new_from_str(yaml).load(&mut collector, false) {
+            Ok(()) => Self {
+                entries: collector.entries,
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Option<Span> {
+        self.entries.get(path).map(|e| e.span)
+    }
+
+    /// Start of the value at `path`, excluding its key
+    pub fn value_start(&self, path: &str) -> Option<usize> {
+        self.entries.get(path).map(|e| e.value_start)
+    }
+
+    pub fn is_mapping(&self, path: &str) -> bool {
+        self.entries.get(path).is_some_and(|e| e.mapping)
+    }
+
+    /// Direct children of a collection as (escaped segment, span), in document order
+    pub fn children(&self, path: &str) -> Vec<(&str, Span)> {
+        let prefix = format!("{}/", path);
+        let mut children: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|(p, e)| {
+                let segment = p.strip_prefix(&prefix)?;
+                (!segment.contains('/')).then_some((segment, e.span))
+            })
+            .collect();
+        children.sort_by_key(|(_, span)| span.start);
+        children
+    }
+
+    /// Span of `path`, or of its nearest ancestor that exists in the document
+    pub fn lookup(&self, path: &str) -> Option<Span> {
+        let mut current = path;
+        loop {
+            if let Some(entry) = self.entries.get(current) {
+                return Some(entry.span);
+            }
+            current = &current[..current.rfind('/')?];
+        }
+    }
+}
+
+/// Escape a mapping key for use as a JSON pointer segment
+pub fn escape_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+enum Frame {
+    Mapping {
+        path: String,
+        start: usize,
+        key: Option<(String, usize)>,
+    },
+    Sequence {
+        path: String,
+        start: usize,
+        index: usize,
+    },
+}
+
+/// Event receiver that records the span of every YAML value
+struct SpanCollector<'a> {
+    yaml: &'a str,
+    /// Byte offset of every char index (plus one past the end)
+    char_bytes: Vec<usize>,
+    stack: Vec<Frame>,
+    entries: HashMap<String, Entry>,
+}
+
+impl<'a> SpanCollector<'a> {
+    fn new(yaml: &'a str) -> Self {
+        let mut char_bytes: Vec<usize> = yaml.char_indices().map(|(i, _)| i).collect();
+        char_bytes.push(yaml.len());
+
+        Self {
+            yaml,
+            char_bytes,
+            stack: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    fn byte(&self, char_index: usize) -> usize {
+        self.char_bytes[char_index.min(self.char_bytes.len() - 1)]
+    }
+
+    /// Path and start offset of the value about to be read, or None for a mapping key
+    fn next_value(&self, value_start: usize) -> Option<(String, usize)> {
+        match self.stack.last() {
+            None => Some((String::new(), value_start)),
+            Some(Frame::Mapping { key: None, .. }) => None,
+            Some(Frame::Mapping {
+                path,
+                key: Some((key, key_start)),
+                ..
+            }) => Some((format!("{}/{}", path, escape_segment(key)), *key_start)),
+            Some(Frame::Sequence { path, index, .. }) => {
+                Some((format!("{}/{}", path, index), value_start))
+            }
+        }
+    }
+
+    fn record(
+        &mut self,
+        path: String,
+        start: usize,
+        value_start: usize,
+        end: usize,
+        mapping: bool,
+    ) {
+        let entry = Entry {
+            span: Span { start, end },
+            value_start,
+            mapping,
+        };
+        self.entries.insert(path, entry);
+    }
+
+    /// Move the parent frame past the value that just finished
+    fn advance(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Mapping { key, .. }) => *key = None,
+            Some(Frame::Sequence { index, .. }) => *index += 1,
+            None => {}
+        }
+    }
+}
+
+impl<'input> SpannedEventReceiver<'input> for SpanCollector<'_> {
+    fn on_event(&mut self, ev: Event<'input>, span: YamlSpan) {
+        let start = self.byte(span.start.index());
+        let end = self.byte(span.end.index());
+
+        match ev {
+            Event::Scalar(value, ..) => match self.next_value(start) {
+                None => {
+                    if let Some(Frame::Mapping { key, .. }) = self.stack.last_mut() {
+                        *key = Some((value.into_owned(), start));
+                    }
+                }
+                Some((path, entry_start)) => {
+                    self.record(path, entry_start, start, end, false);
+                    self.advance();
+                }
+            },
+            Event::Alias(_) => {
+                if let Some((path, entry_start)) = self.next_value(start) {
+                    self.record(path, entry_start, start, end, false);
+                    self.advance();
+                }
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                // Complex keys are not used by Dify; record them under an empty key
+                let (path, entry_start) = self
+                    .next_value(start)
+                    .unwrap_or_else(|| (String::new(), start));
+                let mapping = matches!(ev, Event::MappingStart(..));
+                self.record(path.clone(), entry_start, start, start, mapping);
+                let frame = if mapping {
+                    Frame::Mapping {
+                        path,
+                        start: entry_start,
+                        key: None,
+                    }
+                } else {
+                    Frame::Sequence {
+                        path,
+                        start: entry_start,
+                        index: 0,
+                    }
+                };
+                self.stack.push(frame);
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if let Some(frame) = self.stack.pop() {
+                    let (path, frame_start) = match frame {
+                        Frame::Mapping { path, start, .. } => (path, start),
+                        Frame::Sequence { path, start, .. } => (path, start),
+                    };
+                    // Block collections end at the next key; drop the trailing whitespace
+                    let end = end.max(frame_start);
+                    let trimmed = self.yaml[frame_start..end].trim_end().len();
+                    if let Some(entry) = self.entries.get_mut(&path) {
+                        entry.span.end = frame_start + trimmed;
+                    }
+                    self.advance();
+                }
+            }
+            _ => {}
+        }
+    }
+}
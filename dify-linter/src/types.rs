@@ -1,9 +1,14 @@
+use crate::checks::references::{node_references, VariableRef};
+use crate::config::Config;
+use crate::fix::Fix;
+use crate::rules::{find_rule, Category, Rule};
+use crate::source::Location;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 
 /// Lint error severity
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
@@ -14,66 +19,106 @@ pub enum Severity {
 #[derive(Debug, Clone, Serialize)]
 pub struct LintError {
     pub severity: Severity,
+    /// Stable rule code, e.g. `DL1001`
+    pub code: &'static str,
+    /// Stable rule name, e.g. `edge-target-missing`
+    pub rule: &'static str,
     pub node_id: String,
     pub node_title: String,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fix_hint: Option<String>,
+    /// JSON pointer into the DSL (e.g. `/workflow/graph/nodes/3/data/model`),
+    /// or relative to the node's `data` when it does not start with '/'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Source position, resolved from `path` once the file is linted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    /// Machine-applicable fix, applied by `--fix`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
 }
 
 impl LintError {
-    pub fn error(node_id: &str, node_title: &str, message: &str) -> Self {
+    /// Create a diagnostic for `rule` with the rule's default severity
+    pub fn new(rule: &'static Rule, node_id: &str, node_title: &str, message: &str) -> Self {
         Self {
-            severity: Severity::Error,
+            severity: rule.default_severity,
+            code: rule.code,
+            rule: rule.name,
             node_id: node_id.to_string(),
             node_title: node_title.to_string(),
             message: message.to_string(),
             fix_hint: None,
+            path: None,
+            location: None,
+            fix: None,
         }
     }
 
-    pub fn error_with_hint(node_id: &str, node_title: &str, message: &str, hint: &str) -> Self {
+    pub fn with_hint(
+        rule: &'static Rule,
+        node_id: &str,
+        node_title: &str,
+        message: &str,
+        hint: &str,
+    ) -> Self {
         Self {
-            severity: Severity::Error,
-            node_id: node_id.to_string(),
-            node_title: node_title.to_string(),
-            message: message.to_string(),
             fix_hint: Some(hint.to_string()),
+            ..Self::new(rule, node_id, node_title, message)
         }
     }
 
-    pub fn warning(node_id: &str, node_title: &str, message: &str) -> Self {
-        Self {
-            severity: Severity::Warning,
-            node_id: node_id.to_string(),
-            node_title: node_title.to_string(),
-            message: message.to_string(),
-            fix_hint: None,
-        }
+    /// Point the error at a specific value in the DSL (see `path`)
+    pub fn at(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
     }
 
-    pub fn warning_with_hint(node_id: &str, node_title: &str, message: &str, hint: &str) -> Self {
-        Self {
-            severity: Severity::Warning,
-            node_id: node_id.to_string(),
-            node_title: node_title.to_string(),
-            message: message.to_string(),
-            fix_hint: Some(hint.to_string()),
-        }
+    /// Attach a machine-applicable fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
     }
 }
 
 /// Root Dify DSL structure
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DifyDsl {
     pub app: Option<App>,
     pub workflow: Option<Workflow>,
+    pub model_config: Option<ModelConfig>,
     #[serde(flatten)]
     pub extra: HashMap<String, Value>,
 }
 
+impl DifyDsl {
+    /// App mode, e.g. `workflow`, `advanced-chat` or `completion`
+    pub fn mode(&self) -> Option<&str> {
+        self.app.as_ref()?.mode.as_deref()
+    }
+
+    /// Chat, agent and completion apps are configured by `model_config` instead of a graph
+    pub fn uses_model_config(&self) -> bool {
+        match self.mode() {
+            Some(mode) => MODEL_CONFIG_MODES.contains(&mode),
+            None => self.workflow.is_none() && self.model_config.is_some(),
+        }
+    }
+}
+
+/// App modes configured by `model_config`
+pub const MODEL_CONFIG_MODES: &[&str] = &["chat", "agent-chat", "completion"];
+
+/// App modes configured by a workflow graph
+pub const WORKFLOW_MODES: &[&str] = &["workflow", "advanced-chat"];
+
+/// Selector roots that name a variable scope rather than a node
+pub const VARIABLE_SCOPES: &[&str] = &["sys", "conversation", "env"];
+
 /// App section
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct App {
     pub name: Option<String>,
     pub mode: Option<String>,
@@ -83,16 +128,93 @@ pub struct App {
 }
 
 /// Workflow section
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Workflow {
     pub conversation_variables: Option<Vec<ConversationVariable>>,
+    pub environment_variables: Option<Vec<EnvironmentVariable>>,
     pub graph: Option<Graph>,
     #[serde(flatten)]
     pub extra: HashMap<String, Value>,
 }
 
+/// Configuration of chat, agent-chat and completion apps
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelConfig {
+    pub model: Option<Model>,
+    /// `simple` (pre_prompt) or `advanced` (chat/completion prompt config)
+    pub prompt_type: Option<String>,
+    pub pre_prompt: Option<String>,
+    pub chat_prompt_config: Option<Value>,
+    pub completion_prompt_config: Option<Value>,
+    /// Input fields, each a single-key map from field type to its settings
+    pub user_input_form: Option<Vec<HashMap<String, FormField>>>,
+    pub dataset_configs: Option<DatasetConfigs>,
+    pub agent_mode: Option<AgentMode>,
+    pub file_upload: Option<FileUpload>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Input field of a chat or completion app
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormField {
+    pub variable: Option<String>,
+    pub label: Option<String>,
+    pub required: Option<bool>,
+    pub options: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Knowledge bases attached to a chat or completion app
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatasetConfigs {
+    pub retrieval_model: Option<String>,
+    pub datasets: Option<DatasetList>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatasetList {
+    /// Entries of the form `{dataset: {enabled, id}}`
+    pub datasets: Option<Vec<Value>>,
+}
+
+/// Agent settings of an agent-chat app
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentMode {
+    pub enabled: Option<bool>,
+    pub strategy: Option<String>,
+    pub max_iteration: Option<u32>,
+    pub tools: Option<Vec<AgentTool>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Tool available to an agent
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentTool {
+    pub provider_id: Option<String>,
+    pub provider_type: Option<String>,
+    pub tool_name: Option<String>,
+    pub enabled: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// File upload settings of a chat or completion app
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileUpload {
+    pub enabled: Option<bool>,
+    pub allowed_file_types: Option<Vec<String>>,
+    pub image: Option<Value>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
 /// Conversation variable
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationVariable {
     pub id: Option<String>,
     pub name: Option<String>,
@@ -103,8 +225,21 @@ pub struct ConversationVariable {
     pub extra: HashMap<String, Value>,
 }
 
+/// Environment variable, referenced as `{{#env.NAME#}}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub value: Option<Value>,
+    /// `string`, `number` or `secret`
+    pub value_type: Option<String>,
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
 /// Graph containing nodes and edges
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Graph {
     pub nodes: Option<Vec<Node>>,
     pub edges: Option<Vec<Edge>>,
@@ -113,7 +248,7 @@ pub struct Graph {
 }
 
 /// A workflow node
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Node {
     pub id: Option<String>,
     pub data: Option<NodeData>,
@@ -122,7 +257,7 @@ pub struct Node {
 }
 
 /// Node data containing type-specific configuration
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct NodeData {
     #[serde(rename = "type")]
     pub node_type: Option<String>,
@@ -130,7 +265,7 @@ pub struct NodeData {
     pub model: Option<Model>,
     pub prompt_template: Option<Value>,
     pub conditions: Option<Vec<ConditionGroup>>,
-    pub cases: Option<Vec<Case>>,  // IF/ELSE cases (new format)
+    pub cases: Option<Vec<Case>>, // IF/ELSE cases (new format)
     pub classes: Option<Vec<ClassDefinition>>,
     pub query_variable_selector: Option<Vec<String>>,
     pub variables: Option<Value>,
@@ -140,7 +275,7 @@ pub struct NodeData {
 }
 
 /// Model configuration
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Model {
     pub name: Option<String>,
     pub provider: Option<String>,
@@ -149,7 +284,7 @@ pub struct Model {
 }
 
 /// Condition group for IF/ELSE nodes (legacy format)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConditionGroup {
     pub id: Option<String>,
     pub conditions: Option<Vec<Condition>>,
@@ -157,7 +292,7 @@ pub struct ConditionGroup {
 }
 
 /// Case for IF/ELSE nodes (new format with case_id)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Case {
     pub case_id: Option<String>,
     pub conditions: Option<Vec<Condition>>,
@@ -165,7 +300,7 @@ pub struct Case {
 }
 
 /// Single condition
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Condition {
     pub variable_selector: Option<Vec<String>>,
     pub comparison_operator: Option<String>,
@@ -173,14 +308,14 @@ pub struct Condition {
 }
 
 /// Class definition for Question Classifier
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClassDefinition {
     pub id: Option<String>,
     pub name: Option<String>,
 }
 
 /// Edge connecting nodes
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Edge {
     pub id: Option<String>,
     pub source: Option<String>,
@@ -199,32 +334,123 @@ pub struct LintResult {
     pub warnings: Vec<LintError>,
 }
 
+impl LintResult {
+    /// Keep only secret findings, all raised to errors, and file errors so that a file
+    /// that cannot be read or parsed still fails
+    pub fn deny_secrets(self) -> Self {
+        let errors: Vec<LintError> = self
+            .errors
+            .into_iter()
+            .chain(self.warnings)
+            .filter(|e| {
+                find_rule(e.code)
+                    .is_some_and(|r| matches!(r.category, Category::Security | Category::File))
+            })
+            .map(|mut e| {
+                e.severity = Severity::Error;
+                e
+            })
+            .collect();
+
+        Self {
+            valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Lint result for a single file in a batch run
+#[derive(Debug, Serialize)]
+pub struct FileLintResult {
+    pub file: String,
+    #[serde(flatten)]
+    pub result: LintResult,
+}
+
+/// Totals across all files in a batch run
+#[derive(Debug, Default, Serialize)]
+pub struct LintSummary {
+    pub files: usize,
+    pub failed_files: usize,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl LintSummary {
+    pub fn from_results(results: &[FileLintResult]) -> Self {
+        let mut summary = Self {
+            files: results.len(),
+            ..Self::default()
+        };
+
+        for r in results {
+            if !r.result.valid {
+                summary.failed_files += 1;
+            }
+            summary.errors += r.result.errors.len();
+            summary.warnings += r.result.warnings.len();
+        }
+
+        summary
+    }
+
+    pub fn valid(&self) -> bool {
+        self.failed_files == 0
+    }
+}
+
+/// JSON pointer to the graph nodes list
+pub const NODES_PATH: &str = "/workflow/graph/nodes";
+
+/// JSON pointer to the graph edges list
+pub const EDGES_PATH: &str = "/workflow/graph/edges";
+
 /// Context for linting operations
 pub struct LintContext {
     pub node_ids: HashSet<String>,
     pub node_map: HashMap<String, Node>,
+    pub node_paths: HashMap<String, String>,
     pub edges: Vec<Edge>,
+    pub config: Config,
+    /// Variable references in each node's data, extracted once per lint run
+    references: HashMap<String, Vec<VariableRef>>,
 }
 
 impl LintContext {
-    pub fn new(nodes: &[Node], edges: &[Edge]) -> Self {
+    pub fn new(nodes: &[Node], edges: &[Edge], config: Config) -> Self {
         let mut node_ids = HashSet::new();
         let mut node_map = HashMap::new();
+        let mut node_paths = HashMap::new();
+        let mut references = HashMap::new();
 
-        for node in nodes {
+        for (i, node) in nodes.iter().enumerate() {
             if let Some(id) = &node.id {
                 node_ids.insert(id.clone());
                 node_map.insert(id.clone(), node.clone());
+                node_paths.insert(id.clone(), format!("{}/{}", NODES_PATH, i));
+                references.insert(id.clone(), node_references(node));
             }
         }
 
         Self {
             node_ids,
             node_map,
+            node_paths,
             edges: edges.to_vec(),
+            config,
+            references,
         }
     }
 
+    /// JSON pointer to a node, or to the nodes list if the ID is unknown
+    pub fn node_path(&self, id: &str) -> String {
+        self.node_paths
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| NODES_PATH.to_string())
+    }
+
     pub fn node_exists(&self, id: &str) -> bool {
         self.node_ids.contains(id)
     }
@@ -232,6 +458,41 @@ impl LintContext {
     pub fn get_node(&self, id: &str) -> Option<&Node> {
         self.node_map.get(id)
     }
+
+    /// Every selector and template reference in a node's data
+    pub(crate) fn references(&self, node: &Node) -> &[VariableRef] {
+        node.id
+            .as_deref()
+            .and_then(|id| self.references.get(id))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Node {
+    /// Node type from `data.type`, or "" if missing
+    pub fn node_type(&self) -> &str {
+        self.data
+            .as_ref()
+            .and_then(|d| d.node_type.as_deref())
+            .unwrap_or("")
+    }
+
+    /// Canvas sticky note (`type: custom-note`), which is not part of the workflow
+    pub fn is_note(&self) -> bool {
+        self.extra.get("type").and_then(|t| t.as_str()) == Some("custom-note")
+    }
+
+    /// ID of the iteration or loop node containing this node, from `parentId`
+    /// or else `data.iteration_id` / `data.loop_id`
+    pub fn parent_id(&self) -> Option<&str> {
+        self.extra
+            .get("parentId")
+            .or_else(|| {
+                let extra = &self.data.as_ref()?.extra;
+                extra.get("iteration_id").or_else(|| extra.get("loop_id"))
+            })
+            .and_then(|p| p.as_str())
+    }
 }
 
 impl Clone for Node {
@@ -0,0 +1,59 @@
+use dify_linter::{lint_source, Config, SourceFile};
+
+/// Codes reported for a workflow whose classifier has the given class names
+fn class_codes(names: &[&str]) -> Vec<&'static str> {
+    let classes: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!("        - {{id: '{}', name: '{}'}}", i + 1, name))
+        .collect();
+    let edges: Vec<String> = (1..=names.len())
+        .map(|i| format!("    - {{source: qc, sourceHandle: '{}', target: end}}", i))
+        .collect();
+    let yaml = format!(
+        r#"app: {{mode: workflow, name: classify}}
+workflow:
+  graph:
+    edges:
+    - {{source: start, target: qc}}
+{}
+    nodes:
+    - {{id: start, data: {{type: start, title: Start, variables: [{{variable: q, label: Q, type: paragraph}}]}}}}
+    - id: qc
+      data:
+        type: question-classifier
+        title: QC
+        model: {{provider: openai, name: gpt-4o, mode: chat}}
+        query_variable_selector: [start, q]
+        classes:
+{}
+    - {{id: end, data: {{type: end, title: End, outputs: [{{variable: c, value_selector: [qc, class_name]}}]}}}}
+"#,
+        edges.join("\n"),
+        classes.join("\n")
+    );
+
+    let result = lint_source(&SourceFile::from_yaml(yaml), Config::default());
+    result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|e| e.code)
+        .collect()
+}
+
+#[test]
+fn class_series_are_not_reported_as_similar() {
+    assert!(!class_codes(&["Plan A", "Plan B"]).contains(&"DL3307"));
+    assert!(!class_codes(&["Tier 1", "Tier 2", "Tier 3"]).contains(&"DL3307"));
+}
+
+#[test]
+fn nearly_identical_classes_are_reported() {
+    assert!(class_codes(&["Billing issue", "Billing issues"]).contains(&"DL3307"));
+}
+
+#[test]
+fn distinct_classes_are_clean() {
+    assert_eq!(class_codes(&["Billing", "Shipping"]), Vec::<&str>::new());
+}
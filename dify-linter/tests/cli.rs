@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const SECRET: &str = "sk-proj-Zx81aQpLm3Nv7Rt2Wy5Ub9Kc4Hd6Jf0G";
+
+/// Workflow with an environment variable holding `value`, and an LLM node without a model
+fn workflow(value: &str) -> String {
+    format!(
+        r#"app: {{mode: workflow, name: cli}}
+workflow:
+  environment_variables:
+  - {{name: OPENAI, value: '{}', value_type: secret, id: e1}}
+  graph:
+    edges:
+    - {{source: start, target: llm}}
+    - {{source: llm, target: end}}
+    nodes:
+    - {{id: start, data: {{type: start, title: Start, variables: []}}}}
+    - {{id: llm, data: {{type: llm, title: LLM, prompt_template: [{{role: user, text: hi}}]}}}}
+    - {{id: end, data: {{type: end, title: End, outputs: [{{variable: out, value_selector: [llm, text]}}]}}}}
+"#,
+        value
+    )
+}
+
+/// Exit code and report of `dify-linter --deny-secrets` on a file with `content`
+fn deny_secrets(name: &str, content: &str) -> (i32, String) {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("dify-linter-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_dify-linter"))
+        .arg("--deny-secrets")
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn deny_secrets_fails_on_leaked_credential() {
+    let (code, report) = deny_secrets("secret.yml", &workflow(SECRET));
+    assert_eq!(code, 1);
+    assert!(report.contains("[DL7001]"), "{}", report);
+    assert!(!report.contains("[DL3101]"), "{}", report);
+}
+
+#[test]
+fn deny_secrets_fails_on_credential_in_unparseable_file() {
+    let broken = format!(
+        "app: {{mode: workflow, name: x}}\nworkflow:\n  graph: \"{}\"\n",
+        SECRET
+    );
+    let (code, report) = deny_secrets("broken.yml", &broken);
+    assert_eq!(code, 1);
+    assert!(report.contains("[DL0001]"), "{}", report);
+    assert!(report.contains("[DL7001]"), "{}", report);
+}
+
+#[test]
+fn deny_secrets_ignores_other_errors() {
+    let (code, report) = deny_secrets("clean.yml", &workflow(""));
+    assert_eq!(code, 0, "{}", report);
+}
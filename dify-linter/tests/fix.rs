@@ -0,0 +1,88 @@
+use dify_linter::{fix_file, lint_source, read_source, Config};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const WORKFLOW: &str = r#"app:
+  mode: advanced-chat
+  name: fix
+kind: app
+version: 0.1.5
+workflow:
+  conversation_variables:
+  - id: cv1
+    name: topic
+    description: ''
+  graph:
+    edges:
+    - {source: start, target: ans}
+    nodes:
+    - {id: start, data: {type: start, title: Start, variables: []}}
+    - {id: ans, data: {type: answer, title: Answer, answer: '{{#conversation.topic#}}'}}
+"#;
+
+/// Write `content` to a fresh file in the temp directory
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("dify-linter-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Codes reported for a file on disk
+fn codes(path: &Path) -> Vec<&'static str> {
+    let result = lint_source(&read_source(path).unwrap(), Config::default());
+    result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|e| e.code)
+        .collect()
+}
+
+#[test]
+fn fix_round_trips_yaml() {
+    let path = temp_file("fix.yml", WORKFLOW);
+    assert_eq!(codes(&path), vec!["DL4101", "DL4102"]);
+
+    let fixed = fix_file(&path, Config::default()).unwrap().unwrap();
+    assert_eq!(fixed.fixed, 2);
+    fixed.write().unwrap();
+
+    let remaining = codes(&path);
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(remaining, Vec::<&str>::new());
+    assert!(content.contains("value_type: string"), "{}", content);
+}
+
+#[test]
+fn fix_round_trips_json_export() {
+    let export = serde_json::json!({"result": "success", "data": WORKFLOW}).to_string();
+    let path = temp_file("fix.json", &export);
+    assert_eq!(codes(&path), vec!["DL4101", "DL4102"]);
+
+    let fixed = fix_file(&path, Config::default()).unwrap().unwrap();
+    assert_eq!(fixed.fixed, 2);
+    fixed.write().unwrap();
+
+    let remaining = codes(&path);
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(remaining, Vec::<&str>::new());
+
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["result"], "success");
+    let data = json["data"].as_str().unwrap();
+    assert!(data.contains("value_type: string"), "{}", data);
+}
+
+#[test]
+fn clean_file_has_nothing_to_fix() {
+    let fixed = WORKFLOW.replace(
+        "    description: ''\n",
+        "    description: ''\n    value_type: string\n    value: ''\n",
+    );
+    let path = temp_file("clean.yml", &fixed);
+    let result = fix_file(&path, Config::default());
+    fs::remove_file(&path).unwrap();
+    assert!(result.unwrap().is_none());
+}
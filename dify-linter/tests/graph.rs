@@ -0,0 +1,286 @@
+use dify_linter::{lint_source, Config, SourceFile};
+
+/// (code, node id) of every diagnostic for a workflow with the given edge and node lines
+fn diagnostics(edges: &[&str], nodes: &[&str]) -> Vec<(&'static str, String)> {
+    let yaml = format!(
+        r#"app: {{mode: workflow, name: graph}}
+workflow:
+  graph:
+    edges:
+{}
+    nodes:
+    - {{id: start, data: {{type: start, title: Start, variables: [{{variable: q, label: Q, type: paragraph}}]}}}}
+{}
+"#,
+        edges
+            .iter()
+            .map(|e| format!("    - {}", e))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        nodes
+            .iter()
+            .map(|n| format!("    - {}", n))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+
+    let result = lint_source(&SourceFile::from_yaml(yaml), Config::default());
+    result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|e| (e.code, e.node_id.clone()))
+        .collect()
+}
+
+/// A template node that reads the given selectors
+fn template(id: &str, reads: &[&str]) -> String {
+    let variables: Vec<String> = reads
+        .iter()
+        .enumerate()
+        .map(|(i, source)| format!("{{variable: v{}, value_selector: [{}, output]}}", i, source))
+        .collect();
+    let template: Vec<String> = (0..reads.len())
+        .map(|i| format!("{{{{ v{} }}}}", i))
+        .collect();
+    format!(
+        "{{id: {id}, data: {{type: template-transform, title: {id}, template: '{}x', variables: [{}]}}}}",
+        template.join(" "),
+        variables.join(", ")
+    )
+}
+
+fn end(id: &str, source: &str) -> String {
+    format!(
+        "{{id: {id}, data: {{type: end, title: {id}, outputs: [{{variable: out, value_selector: [{source}, output]}}]}}}}"
+    )
+}
+
+fn has(diagnostics: &[(&str, String)], code: &str, node: &str) -> bool {
+    diagnostics.iter().any(|(c, n)| *c == code && n == node)
+}
+
+#[test]
+fn linear_workflow_is_clean() {
+    let found = diagnostics(
+        &["{source: start, target: a}", "{source: a, target: end}"],
+        &[&template("a", &[]), &end("end", "a")],
+    );
+    assert_eq!(found, vec![]);
+}
+
+#[test]
+fn unconnected_node_is_orphaned() {
+    let found = diagnostics(
+        &["{source: start, target: a}", "{source: a, target: end}"],
+        &[
+            &template("a", &[]),
+            &template("lost", &[]),
+            &end("end", "a"),
+        ],
+    );
+    assert!(has(&found, "DL1003", "lost"));
+}
+
+#[test]
+fn node_fed_only_by_a_detached_node_is_unreachable() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: a, target: end}",
+            "{source: island, target: b}",
+            "{source: b, target: end}",
+        ],
+        &[
+            &template("a", &[]),
+            &template("island", &[]),
+            &template("b", &[]),
+            &end("end", "a"),
+        ],
+    );
+    assert!(has(&found, "DL1004", "b"));
+    assert!(!has(&found, "DL1004", "a"));
+}
+
+#[test]
+fn branch_that_never_finishes_is_a_dead_end() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: start, target: stuck}",
+            "{source: a, target: end}",
+        ],
+        &[
+            &template("a", &[]),
+            &template("stuck", &[]),
+            &end("end", "a"),
+        ],
+    );
+    assert!(has(&found, "DL1005", "stuck"));
+    assert!(!has(&found, "DL1005", "a"));
+}
+
+#[test]
+fn back_edge_forms_a_cycle() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: a, target: b}",
+            "{source: b, target: a}",
+            "{source: b, target: end}",
+        ],
+        &[&template("a", &[]), &template("b", &[]), &end("end", "b")],
+    );
+    let cycles: Vec<_> = found.iter().filter(|(c, _)| *c == "DL1006").collect();
+    assert_eq!(cycles, vec![&("DL1006", "a".to_string())]);
+}
+
+#[test]
+fn self_loop_is_a_cycle() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: a, target: a}",
+            "{source: a, target: end}",
+        ],
+        &[&template("a", &[]), &end("end", "a")],
+    );
+    assert!(has(&found, "DL1006", "a"));
+}
+
+#[test]
+fn iteration_body_is_not_a_cycle() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: it}",
+            "{source: it, target: end}",
+            "{source: itstart, target: inner}",
+        ],
+        &[
+            "{id: it, data: {type: iteration, title: It, iterator_selector: [sys, files], \
+             output_selector: [inner, output], start_node_id: itstart}}",
+            "{id: itstart, parentId: it, data: {type: iteration-start, title: '', isInIteration: true}}",
+            "{id: inner, parentId: it, data: {type: template-transform, title: Inner, template: x, \
+             isInIteration: true, iteration_id: it, variables: [{variable: v, value_selector: [it, item]}]}}",
+            &end("end", "it"),
+        ],
+    );
+    assert!(found.iter().all(|(c, _)| *c != "DL1006"), "{:?}", found);
+}
+
+fn if_else(id: &str) -> String {
+    format!(
+        "{{id: {id}, data: {{type: if-else, title: {id}, cases: [{{case_id: 'true', logical_operator: and, \
+         conditions: [{{comparison_operator: contains, value: x, variable_selector: [start, q]}}]}}]}}}}"
+    )
+}
+
+#[test]
+fn end_output_from_one_branch_runs_on_some_paths() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: ie}",
+            "{source: ie, sourceHandle: 'true', target: a}",
+            "{source: ie, sourceHandle: 'false', target: b}",
+            "{source: a, target: end}",
+            "{source: b, target: end}",
+        ],
+        &[
+            &if_else("ie"),
+            &template("a", &[]),
+            &template("b", &[]),
+            &end("end", "a"),
+        ],
+    );
+    assert!(has(&found, "DL3992", "end"));
+}
+
+#[test]
+fn end_output_after_parallel_join_is_available() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: start, target: b}",
+            "{source: a, target: end}",
+            "{source: b, target: end}",
+        ],
+        &[&template("a", &[]), &template("b", &[]), &end("end", "a")],
+    );
+    assert!(!has(&found, "DL3992", "end"), "{:?}", found);
+}
+
+#[test]
+fn end_output_from_node_after_end_is_unavailable() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: a, target: end}",
+            "{source: start, target: late}",
+        ],
+        &[
+            &template("a", &[]),
+            &template("late", &[]),
+            &end("end", "late"),
+        ],
+    );
+    assert!(has(&found, "DL3992", "end"));
+}
+
+#[test]
+fn reading_a_later_node_has_not_run() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: a, target: b}",
+            "{source: b, target: end}",
+        ],
+        &[
+            &template("a", &["b"]),
+            &template("b", &[]),
+            &end("end", "b"),
+        ],
+    );
+    assert!(has(&found, "DL4008", "a"));
+}
+
+#[test]
+fn reading_one_branch_after_if_else_runs_on_some_paths() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: ie}",
+            "{source: ie, sourceHandle: 'true', target: a}",
+            "{source: ie, sourceHandle: 'false', target: b}",
+            "{source: a, target: c}",
+            "{source: b, target: c}",
+            "{source: c, target: end}",
+        ],
+        &[
+            &if_else("ie"),
+            &template("a", &[]),
+            &template("b", &[]),
+            &template("c", &["a"]),
+            &end("end", "c"),
+        ],
+    );
+    assert!(has(&found, "DL4009", "c"));
+}
+
+#[test]
+fn reading_a_branch_after_parallel_join_is_clean() {
+    let found = diagnostics(
+        &[
+            "{source: start, target: a}",
+            "{source: start, target: b}",
+            "{source: a, target: c}",
+            "{source: b, target: c}",
+            "{source: c, target: end}",
+        ],
+        &[
+            &template("a", &[]),
+            &template("b", &[]),
+            &template("c", &["a", "b"]),
+            &end("end", "c"),
+        ],
+    );
+    assert_eq!(found, vec![]);
+}